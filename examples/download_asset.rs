@@ -1,4 +1,4 @@
-use rust_wistia::models::DownloadAssetRequest;
+use rust_wistia::models::{AssetType, DownloadAssetRequest};
 use rust_wistia::{Result, WistiaClient};
 
 #[macro_use]
@@ -19,7 +19,7 @@ struct Args {
 
     /// Type of the media asset to download. Defaults to the original asset that was uploaded.
     #[clap(short = 't', long, default_value = "OriginalFile")]
-    asset_type: String,
+    asset_type: AssetType,
 
     /// Path to media file
     #[clap(short, long, parse(from_os_str), default_value = "./my-video.mp4")]
@@ -57,7 +57,7 @@ async fn main() -> Result<()> {
     // Normally, we'd just use `DownloadAssetRequest::from(&args.video_id)`, but
     // here we've just retrieved the media info above.
     let info = DownloadAssetRequest::from(media)
-        .asset_type(&args.asset_type)
+        .asset_type(args.asset_type)
         .file_path(&args.file_path);
 
     let _content = client.download_asset(info).await?;
@@ -1,3 +1,4 @@
+use rust_wistia::models::AssetType;
 use rust_wistia::{Result, WistiaClient};
 
 #[macro_use]
@@ -16,7 +17,7 @@ struct Args {
 
     /// Type of the media asset to retrieve. Defaults to the original asset that was uploaded.
     #[clap(short = 't', long, default_value = "OriginalFile")]
-    asset_type: String,
+    asset_type: AssetType,
 
     /// Retrieve only the HTTP asset url, rather than the SSL (HTTPS) variant
     #[clap(short, long)]
@@ -28,7 +29,7 @@ async fn main() -> Result<()> {
     sensible_env_logger::init!();
 
     let args: Args = Args::parse();
-    let asset_type = args.asset_type.as_str();
+    let asset_type = args.asset_type;
 
     // Alternatively, we could use `WistiaClient::from(token)?` to
     // create the new `WistiaClient` instance.
@@ -39,10 +40,10 @@ async fn main() -> Result<()> {
     let media = client.get_media(video_id).await?;
 
     let url = if args.no_ssl {
-        media.asset_url_insecure(asset_type)?.to_owned()
+        media.asset_url_insecure(asset_type.as_str())?.to_owned()
     } else {
         // as a shorthand, we could just call `media.source_url()?` in this case
-        media.asset_url(asset_type)?
+        media.asset_url(asset_type.as_str())?
     };
 
     trace!("Asset URL: {url}");
@@ -37,11 +37,10 @@ async fn main() -> Result<()> {
     let client = WistiaClient::from_env()?;
 
     let req = UpdateMediaRequest {
-        id: args.video_id,
+        id: args.video_id.parse()?,
         name: args.name,
-        new_still_media_id: args.still_media_id,
+        new_still_media_id: args.still_media_id.map(|id| id.parse()).transpose()?,
         description: args.description,
-        ..Default::default()
     };
 
     let res = client.update_media(req).await?;
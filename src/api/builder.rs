@@ -0,0 +1,715 @@
+//! Builder to configure the HTTPS client and the request options shared by
+//! the Wistia API clients.
+//!
+use crate::api::certs::{self, Certificate, Identity};
+use crate::api::client::{UploadAuth, UploadClient};
+use crate::api::proxy::{Proxy, ProxyConnector};
+use crate::api::rate_limit::{RateLimit, RateLimitLayer, RateLimiter, RequestLimiter};
+use crate::api::retry::{Replay, Retry, RetryLayer, RetryPolicy};
+use crate::api::transport::{BoxError, BoxTransport, HttpsTransport, Transport};
+use crate::api::{ClientRegistry, DataClient, Wistia};
+use crate::auth::{AccessToken, TokenProvider};
+use crate::constants::{
+    DATA_API, DATA_API_ENV_VAR_NAME, DEFAULT_USER_AGENT, ENV_VAR_NAME, UPLOAD_API,
+    UPLOAD_API_ENV_VAR_NAME,
+};
+use crate::https::{tls, HttpsClient};
+#[cfg(not(any(feature = "rust-tls", feature = "http2")))]
+use crate::log::warn;
+use crate::types::Result;
+use crate::utils::validate_base_url;
+use crate::RustWistiaError;
+
+use std::env::var;
+use std::fmt;
+use std::future::poll_fn;
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyper::body::HttpBody;
+use hyper::client::HttpConnector;
+use hyper::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
+use hyper::{Body, Client, Request, Response};
+#[cfg(feature = "rust-tls")]
+use rustls::ClientConfig;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// The HTTP version(s) to use for requests.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HttpVersion {
+    /// Negotiate HTTP/2 or HTTP/1.1 with the server, via TLS [ALPN] (the
+    /// default). A plain HTTP connection, such as to a local mock server,
+    /// uses HTTP/1.1.
+    ///
+    /// [ALPN]: https://en.wikipedia.org/wiki/Application-Layer_Protocol_Negotiation
+    #[default]
+    Auto,
+    /// Only use HTTP/1.1.
+    Http1,
+    /// Only use HTTP/2, with *prior knowledge* on a plain HTTP connection.
+    ///
+    /// # Note
+    /// With the `native-tls` feature, this additionally requires the `http2`
+    /// feature to be enabled.
+    Http2,
+}
+
+/// Builder for the Wistia API clients, which configures the underlying
+/// HTTPS client (timeouts, connection pool and HTTP version) and the
+/// options applied to each request.
+///
+/// Any setting which is not specified falls back to the same default as
+/// `from_env()`, so the access token and base URLs are read from the
+/// environment if they are not set.
+///
+/// Requests are sent by a [`tower::Service`], which can be wrapped in any
+/// middleware [`Layer`] via [`ClientBuilder::layer`].
+///
+/// [`tower::Service`]: tower_service::Service
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use std::time::Duration;
+/// use rust_wistia::{ClientBuilder, HttpVersion};
+///
+/// let client = ClientBuilder::new()
+///     .access_token("my-token")
+///     .connect_timeout(Duration::from_secs(5))
+///     .timeout(Duration::from_secs(30))
+///     .user_agent("my-app/1.0")
+///     .http_version(HttpVersion::Http1)
+///     .build_data()?;
+///
+/// let media = client.get_media("abc1234567").await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ClientBuilder {
+    access_token: Option<AccessToken>,
+    token_provider: Option<Provider>,
+    base_url: Option<String>,
+    upload_url: Option<String>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    headers: Vec<(String, String)>,
+    pool_idle_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    http_version: HttpVersion,
+    retry_policy: RetryPolicy,
+    pub(crate) rate_limit: Option<RateLimit>,
+    pub(crate) tenant_rate_limit: Option<RateLimit>,
+    pub(crate) upload_auth: UploadAuth,
+    proxies: Option<Vec<Proxy>>,
+    root_certificates: Vec<Certificate>,
+    identity: Option<Identity>,
+    #[cfg(feature = "rust-tls")]
+    tls_config: Option<TlsConfig>,
+    #[cfg(not(feature = "rust-tls"))]
+    tls_connector: Option<native_tls::TlsConnector>,
+    layers: Vec<TransportLayer>,
+}
+
+impl ClientBuilder {
+    /// Create a new `ClientBuilder` with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the [API access token] used to authenticate requests. If not
+    /// set, this is read from the `WISTIA_API_TOKEN` environment variable.
+    ///
+    /// [API access token]: https://wistia.com/support/developers/data-api#getting-started
+    pub fn access_token(mut self, access_token: impl Into<AccessToken>) -> Self {
+        self.access_token = Some(access_token.into());
+        self
+    }
+
+    /// Sets a [`TokenProvider`] which is consulted for the access token on
+    /// each call, such as to read a rotated token from a file or a secret
+    /// manager. This takes precedence over [`ClientBuilder::access_token`].
+    pub fn token_provider(mut self, provider: impl TokenProvider) -> Self {
+        self.token_provider = Some(Provider(Arc::new(provider)));
+        self
+    }
+
+    /// Sets the base URL of the Wistia Data API. If not set, this is read
+    /// from the `WISTIA_API_URL` environment variable, falling back to
+    /// `https://api.wistia.com/v1`.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.to_owned());
+        self
+    }
+
+    /// Sets the base URL of the Wistia Upload API. If not set, this is read
+    /// from the `WISTIA_UPLOAD_URL` environment variable, falling back to
+    /// `https://upload.wistia.com`.
+    pub fn upload_url(mut self, upload_url: &str) -> Self {
+        self.upload_url = Some(upload_url.to_owned());
+        self
+    }
+
+    /// Sets the timeout for establishing a new connection (default: none).
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for each read, that is while waiting on the
+    /// response headers or the next chunk of the response body
+    /// (default: none).
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the total timeout for each attempt of a request, from when it
+    /// is sent until the response body is fully read (default: none).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with each request (default:
+    /// `rust-wistia/<version>`).
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Adds a header which is sent with each request, unless the request
+    /// already sets it.
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets how long an idle connection is kept open in the pool (default:
+    /// 90 seconds).
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum number of idle connections per host in the pool
+    /// (default: no limit).
+    pub fn pool_max_idle_per_host(mut self, max_idle: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max_idle);
+        self
+    }
+
+    /// Sets the HTTP version(s) to use for requests (default:
+    /// [`HttpVersion::Auto`]).
+    pub fn http_version(mut self, version: HttpVersion) -> Self {
+        self.http_version = version;
+        self
+    }
+
+    /// Sets the policy to retry failed requests (default: up to 3 attempts
+    /// for idempotent requests).
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Sets a limit on the rate of requests, so that requests wait locally
+    /// rather than exceed the account's quota (default: none).
+    ///
+    /// The limit is shared by all clients with the same access token, even
+    /// if built separately, and a clone from `for_token()` is limited by
+    /// its own token. With a [`ClientBuilder::token_provider`], the limit
+    /// is shared by the clones of the built client.
+    pub fn rate_limit(mut self, limit: RateLimit) -> Self {
+        self.rate_limit = Some(limit);
+        self
+    }
+
+    /// Sets a limit on the rate of requests for each tenant of a
+    /// [`ClientRegistry`] (default: none).
+    ///
+    /// The limit set via [`ClientBuilder::rate_limit`] is then shared by
+    /// all tenants of the registry.
+    pub fn tenant_rate_limit(mut self, limit: RateLimit) -> Self {
+        self.tenant_rate_limit = Some(limit);
+        self
+    }
+
+    /// Sets how the access token is sent with each request to the Upload
+    /// API (default: as a bearer token in the `Authorization` header).
+    pub fn upload_auth(mut self, auth: UploadAuth) -> Self {
+        self.upload_auth = auth;
+        self
+    }
+
+    /// Sends requests through a [`Proxy`]; this can be called more than
+    /// once, in which case the first proxy which applies to a request is
+    /// used.
+    ///
+    /// If no proxy is added, the proxies set in the environment via the
+    /// `HTTPS_PROXY`, `HTTP_PROXY` and `NO_PROXY` variables are used.
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.get_or_insert_with(Vec::new).push(proxy);
+        self
+    }
+
+    /// Connects directly to the Wistia API, ignoring any proxies set in the
+    /// environment or added via [`ClientBuilder::proxy`].
+    pub fn no_proxy(mut self) -> Self {
+        self.proxies = Some(Vec::new());
+        self
+    }
+
+    /// Trusts a root certificate, in addition to the platform's native
+    /// roots, such as the CA of a TLS-intercepting gateway.
+    pub fn add_root_certificate(mut self, cert: Certificate) -> Self {
+        self.root_certificates.push(cert);
+        self
+    }
+
+    /// Sets a client certificate to present to the server, for mutual TLS.
+    pub fn identity(mut self, identity: Identity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// Sets the `rustls` configuration to use for TLS, as-is; any root
+    /// certificates or identity set on the builder are ignored.
+    ///
+    /// The ALPN protocols are set from [`ClientBuilder::http_version`].
+    #[cfg(feature = "rust-tls")]
+    pub fn rustls_config(mut self, config: ClientConfig) -> Self {
+        self.tls_config = Some(TlsConfig(Arc::new(config)));
+        self
+    }
+
+    /// Sets the `native-tls` connector to use for TLS, as-is; any root
+    /// certificates or identity set on the builder are ignored.
+    #[cfg(not(feature = "rust-tls"))]
+    pub fn native_tls_connector(mut self, connector: native_tls::TlsConnector) -> Self {
+        self.tls_connector = Some(connector);
+        self
+    }
+
+    /// Wraps the transport, which sends each request, in a middleware
+    /// [`Layer`] such as a concurrency limit or a tracing layer from the
+    /// [`tower`] ecosystem.
+    ///
+    /// Layers are applied in the order they are added, around the retries
+    /// and rate limit, so that the last layer added is the first to see a
+    /// request. Any errors raised by a layer are returned as a
+    /// [`RustWistiaError::Transport`].
+    ///
+    /// [`tower`]: https://docs.rs/tower
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<BoxTransport> + Send + Sync + 'static,
+        L::Service: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+        <L::Service as Service<Request<Body>>>::Error: Into<BoxError>,
+        <L::Service as Service<Request<Body>>>::Future: Send + 'static,
+    {
+        self.layers.push(TransportLayer(Arc::new(move |transport| {
+            BoxTransport::new(layer.layer(transport))
+        })));
+        self
+    }
+
+    /// Build a client for the Wistia [Data API].
+    ///
+    /// [Data API]: https://wistia.com/support/developers/data-api
+    pub fn build_data(self) -> Result<DataClient> {
+        let (provider, token) = self.resolve_token()?;
+        let options = self.request_options(token.as_ref())?;
+
+        Ok(DataClient::from_parts(
+            provider,
+            self.resolve_base_url()?,
+            options,
+        ))
+    }
+
+    /// Build a client for the Wistia [Upload API].
+    ///
+    /// [Upload API]: https://wistia.com/support/developers/upload-api
+    pub fn build_upload(self) -> Result<UploadClient> {
+        let (provider, token) = self.resolve_token()?;
+        let options = self.request_options(token.as_ref())?;
+
+        Ok(
+            UploadClient::from_parts(provider, self.resolve_upload_url()?, options)
+                .with_auth(self.upload_auth),
+        )
+    }
+
+    /// Build a [`Wistia`] client, which shares one connection pool between
+    /// the Data, Upload and Stats APIs.
+    pub fn build(self) -> Result<Wistia> {
+        let (provider, token) = self.resolve_token()?;
+        let options = self.request_options(token.as_ref())?;
+
+        Ok(Wistia::from_clients(
+            DataClient::from_parts(provider.clone(), self.resolve_base_url()?, options.clone()),
+            UploadClient::from_parts(provider, self.resolve_upload_url()?, options)
+                .with_auth(self.upload_auth),
+        ))
+    }
+
+    /// Build a [`ClientRegistry`], which lazily builds a client for each
+    /// tenant with the access token from `resolver`; the clients share one
+    /// connection pool and the [rate limit](ClientBuilder::rate_limit).
+    ///
+    /// The access token (or [`ClientBuilder::token_provider`]) set on the
+    /// builder is ignored.
+    pub fn build_registry<F, P>(self, resolver: F) -> Result<ClientRegistry>
+    where
+        F: Fn(&str) -> Result<P> + Send + Sync + 'static,
+        P: TokenProvider,
+    {
+        ClientRegistry::from_builder(self, resolver)
+    }
+
+    /// Build the HTTPS client, with the configured connection pool, connect
+    /// timeout, HTTP version, proxies and TLS settings.
+    ///
+    /// Returns an error if the TLS configuration cannot be built, such as
+    /// when the native root certificates cannot be loaded.
+    pub fn https_client<B>(&self) -> Result<HttpsClient<B>>
+    where
+        B: HttpBody + Send,
+        B::Data: Send,
+    {
+        let mut http = HttpConnector::new();
+        http.enforce_http(false);
+        http.set_connect_timeout(self.connect_timeout);
+
+        let proxies = match &self.proxies {
+            Some(proxies) => proxies.clone(),
+            None => Proxy::from_env(),
+        };
+        let http = ProxyConnector::new(http, proxies);
+
+        #[cfg(feature = "rust-tls")]
+        let https_connector = {
+            let config = match &self.tls_config {
+                Some(config) => ClientConfig::clone(&config.0),
+                None => certs::rustls_config(&self.root_certificates, self.identity.as_ref())?,
+            };
+
+            let builder = tls::HttpsConnectorBuilder::new()
+                .with_tls_config(config)
+                .https_or_http();
+
+            match self.http_version {
+                HttpVersion::Auto => builder.enable_all_versions().wrap_connector(http),
+                HttpVersion::Http1 => builder.enable_http1().wrap_connector(http),
+                HttpVersion::Http2 => builder.enable_http2().wrap_connector(http),
+            }
+        };
+
+        #[cfg(not(feature = "rust-tls"))]
+        let https_connector = {
+            let connector = match &self.tls_connector {
+                Some(connector) => connector.clone(),
+                None => {
+                    certs::native_tls_connector(&self.root_certificates, self.identity.as_ref())?
+                }
+            };
+
+            tls::HttpsConnector::from((http, connector.into()))
+        };
+
+        let mut builder = Client::builder();
+
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max_idle) = self.pool_max_idle_per_host {
+            builder.pool_max_idle_per_host(max_idle);
+        }
+
+        if self.http_version == HttpVersion::Http2 {
+            #[cfg(any(feature = "rust-tls", feature = "http2"))]
+            builder.http2_only(true);
+
+            #[cfg(not(any(feature = "rust-tls", feature = "http2")))]
+            warn!("HTTP/2 requires the `http2` feature; falling back to HTTP/1.1");
+        }
+
+        Ok(builder.build(https_connector))
+    }
+
+    /// Returns the options which are applied to each request, with the
+    /// rate limiter shared by all clients with the access `token`, if known.
+    fn request_options(&self, token: Option<&AccessToken>) -> Result<RequestOptions> {
+        let limiter = self.rate_limit.map(|limit| match token {
+            Some(token) => RateLimiter::shared(token, limit),
+            None => Arc::new(RateLimiter::new(limit)),
+        });
+
+        Ok(RequestOptions {
+            rate_limit: self.rate_limit,
+            limiter,
+            ..RequestOptions::from_parts(self.default_headers()?, self.transport()?)
+        })
+    }
+
+    /// Returns the headers which are sent with each request.
+    pub(crate) fn default_headers(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+
+        let user_agent = self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT);
+        headers.insert(USER_AGENT, header_value(user_agent)?);
+
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(hyper::http::Error::from)?;
+            headers.append(name, header_value(value)?);
+        }
+
+        Ok(headers)
+    }
+
+    /// Build the transport which sends each request, wrapped in the retry
+    /// and rate limit layers, along with any layers added by the user.
+    ///
+    /// The rate limiter is set on each request by the [`RequestOptions`],
+    /// so that clients which share the transport can each have their own.
+    fn transport(&self) -> Result<BoxTransport> {
+        let mut transport = BoxTransport::new(self.https_transport()?);
+
+        if self.rate_limit.is_some() {
+            transport = BoxTransport::new(RateLimitLayer::per_request().layer(transport));
+        }
+
+        Ok(self.wrap_transport(transport, None))
+    }
+
+    /// Build the transport which sends a single request with the HTTPS
+    /// client, with the configured timeouts.
+    pub(crate) fn https_transport(&self) -> Result<HttpsTransport> {
+        Ok(HttpsTransport::new(self.https_client()?)
+            .read_timeout(self.read_timeout)
+            .timeout(self.timeout))
+    }
+
+    /// Wrap a `transport` in the rate `limiters` (the last of which is the
+    /// first to be waited on) and the retry layer, along with any layers
+    /// added by the user.
+    pub(crate) fn wrap_transport(
+        &self,
+        mut transport: BoxTransport,
+        limiters: impl IntoIterator<Item = Arc<RateLimiter>>,
+    ) -> BoxTransport {
+        for limiter in limiters {
+            transport = BoxTransport::new(RateLimitLayer::from_limiter(limiter).layer(transport));
+        }
+
+        transport = BoxTransport::new(RetryLayer::new(self.retry_policy.clone()).layer(transport));
+
+        for layer in &self.layers {
+            transport = (layer.0)(transport);
+        }
+
+        transport
+    }
+
+    /// Returns the provider of the access token, which is the token itself
+    /// unless set via [`ClientBuilder::token_provider`], along with the
+    /// token if it is known up front.
+    fn resolve_token(&self) -> Result<(Arc<dyn TokenProvider>, Option<AccessToken>)> {
+        if let Some(provider) = &self.token_provider {
+            return Ok((provider.0.clone(), None));
+        }
+
+        let token = match &self.access_token {
+            Some(token) => token.clone(),
+            None => var(ENV_VAR_NAME).map(AccessToken::from).map_err(|_| {
+                RustWistiaError::EnvVarNotFound {
+                    name: ENV_VAR_NAME.to_owned(),
+                }
+            })?,
+        };
+
+        Ok((Arc::new(token.clone()), Some(token)))
+    }
+
+    pub(crate) fn resolve_base_url(&self) -> Result<String> {
+        resolve_url(&self.base_url, DATA_API_ENV_VAR_NAME, DATA_API)
+    }
+
+    pub(crate) fn resolve_upload_url(&self) -> Result<String> {
+        resolve_url(&self.upload_url, UPLOAD_API_ENV_VAR_NAME, UPLOAD_API)
+    }
+}
+
+/// Resolve a base URL from the builder, or else the environment, falling
+/// back to the `default` URL.
+fn resolve_url(url: &Option<String>, env_var: &str, default: &str) -> Result<String> {
+    match url.clone().or_else(|| var(env_var).ok()) {
+        Some(url) => validate_base_url(&url),
+        None => Ok(default.to_owned()),
+    }
+}
+
+fn header_value(value: &str) -> Result<HeaderValue> {
+    Ok(HeaderValue::from_str(value).map_err(hyper::http::Error::from)?)
+}
+
+/// A `rustls` configuration set via [`ClientBuilder::rustls_config`].
+#[cfg(feature = "rust-tls")]
+#[derive(Clone)]
+struct TlsConfig(Arc<ClientConfig>);
+
+#[cfg(feature = "rust-tls")]
+impl fmt::Debug for TlsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TlsConfig")
+    }
+}
+
+/// A token provider set via [`ClientBuilder::token_provider`].
+#[derive(Clone)]
+struct Provider(Arc<dyn TokenProvider>);
+
+impl fmt::Debug for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TokenProvider")
+    }
+}
+
+/// A middleware layer added via [`ClientBuilder::layer`].
+#[derive(Clone)]
+struct TransportLayer(Arc<dyn Fn(BoxTransport) -> BoxTransport + Send + Sync>);
+
+impl fmt::Debug for TransportLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TransportLayer")
+    }
+}
+
+/// Options which are applied to each request sent by a client, along with
+/// the transport which sends it.
+#[derive(Clone)]
+pub(crate) struct RequestOptions<T = BoxTransport> {
+    headers: HeaderMap,
+    transport: T,
+    rate_limit: Option<RateLimit>,
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl RequestOptions {
+    /// The default options, which send requests with the HTTPS `client`.
+    pub(crate) fn new(client: HttpsClient) -> Self {
+        let transport = RetryLayer::default().layer(HttpsTransport::new(client));
+
+        Self::with_transport(BoxTransport::new(transport))
+    }
+}
+
+impl<T: Transport> RequestOptions<T> {
+    /// The default options, which send requests with the `transport`.
+    pub(crate) fn with_transport(transport: T) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_static(DEFAULT_USER_AGENT));
+
+        Self::from_parts(headers, transport)
+    }
+
+    /// Create the options from the default `headers` and the `transport`.
+    pub(crate) fn from_parts(headers: HeaderMap, transport: T) -> Self {
+        Self {
+            headers,
+            transport,
+            rate_limit: None,
+            limiter: None,
+        }
+    }
+
+    /// Returns a copy of the options for a client with the access `token`,
+    /// which waits on the rate limiter shared by that token instead.
+    pub(crate) fn for_token(&self, token: &AccessToken) -> Self {
+        Self {
+            limiter: self
+                .rate_limit
+                .map(|limit| RateLimiter::shared(token, limit)),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the transport which sends each request.
+    pub(crate) fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Send a request with the default headers applied, via the transport.
+    ///
+    /// `retry` determines which requests can be sent again, and `replay`
+    /// builds the request again for each retry; without it, only a request
+    /// with an empty body is retried.
+    pub(crate) async fn send(
+        &self,
+        mut req: Request<Body>,
+        retry: Retry,
+        replay: Option<Replay>,
+    ) -> Result<Response<Body>> {
+        apply_headers(&self.headers, &mut req);
+
+        req.extensions_mut().insert(retry);
+        if let Some(limiter) = &self.limiter {
+            req.extensions_mut().insert(RequestLimiter(limiter.clone()));
+        }
+        if let Some(replay) = replay {
+            let headers = self.headers.clone();
+            let replay = replay.map(move |req| apply_headers(&headers, req));
+            req.extensions_mut().insert(replay);
+        }
+
+        let mut transport = self.transport.clone();
+        poll_fn(|cx| transport.poll_ready(cx)).await?;
+
+        transport.call(req).await
+    }
+}
+
+/// Add the default `headers` to a request, unless it already sets them.
+fn apply_headers(headers: &HeaderMap, req: &mut Request<Body>) {
+    for name in headers.keys() {
+        if !req.headers().contains_key(name) {
+            for value in headers.get_all(name) {
+                req.headers_mut().append(name, value.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_options() {
+        let headers = ClientBuilder::new()
+            .user_agent("my-app/1.0")
+            .default_header("X-Request-Source", "tests")
+            .default_headers()
+            .unwrap();
+
+        assert_eq!(headers[USER_AGENT], "my-app/1.0");
+        assert_eq!(headers["x-request-source"], "tests");
+
+        let err = ClientBuilder::new()
+            .default_header("Bad Header", "value")
+            .default_headers()
+            .unwrap_err();
+
+        assert!(matches!(err, RustWistiaError::Http(_)));
+    }
+
+    #[test]
+    fn test_http_version_is_negotiated_by_default() {
+        // HTTP/2 is never forced (with prior knowledge) unless opted in to,
+        // even with the `http2` feature.
+        assert_eq!(HttpVersion::default(), HttpVersion::Auto);
+        assert_eq!(ClientBuilder::new().http_version, HttpVersion::Auto);
+    }
+}
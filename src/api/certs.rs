@@ -0,0 +1,256 @@
+//! Custom TLS configuration for the HTTPS client, such as an extra root
+//! certificate for a TLS-intercepting gateway, or a client certificate for
+//! mutual TLS.
+//!
+use crate::types::Result;
+use crate::RustWistiaError;
+
+use std::fmt;
+use std::io::BufReader;
+
+use rustls_pemfile::Item;
+
+/// A certificate which is trusted as a root, in addition to the platform's
+/// native roots, via [`ClientBuilder::add_root_certificate`].
+///
+/// [`ClientBuilder::add_root_certificate`]: crate::ClientBuilder::add_root_certificate
+#[derive(Clone)]
+pub struct Certificate {
+    der: Vec<Vec<u8>>,
+}
+
+impl Certificate {
+    /// Parse one or more certificates in PEM format, such as a CA bundle.
+    pub fn from_pem(pem: &[u8]) -> Result<Self> {
+        let der = read_pem(pem)?
+            .into_iter()
+            .filter_map(|item| match item {
+                Item::X509Certificate(der) => Some(der),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if der.is_empty() {
+            return Err(tls_error("no certificate found in PEM"));
+        }
+
+        Ok(Self { der })
+    }
+
+    /// A single certificate in DER format.
+    pub fn from_der(der: &[u8]) -> Self {
+        Self {
+            der: vec![der.to_vec()],
+        }
+    }
+}
+
+impl fmt::Debug for Certificate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Certificate")
+            .field("count", &self.der.len())
+            .finish()
+    }
+}
+
+/// A client certificate and its private key, which are presented to the
+/// server for mutual TLS via [`ClientBuilder::identity`].
+///
+/// [`ClientBuilder::identity`]: crate::ClientBuilder::identity
+#[derive(Clone)]
+pub struct Identity {
+    /// The certificate chain, in DER format, starting with the client's own
+    /// certificate.
+    certs: Vec<Vec<u8>>,
+    key: Key,
+}
+
+#[derive(Clone)]
+enum Key {
+    Pkcs8(Vec<u8>),
+    Rsa(Vec<u8>),
+    Ec(Vec<u8>),
+}
+
+impl Key {
+    /// Returns the key in DER format.
+    fn der(&self) -> &[u8] {
+        match self {
+            Self::Pkcs8(der) | Self::Rsa(der) | Self::Ec(der) => der,
+        }
+    }
+}
+
+impl Identity {
+    /// Parse a client certificate (chain) and private key, both in PEM
+    /// format.
+    ///
+    /// # Note
+    /// With the `native-tls` feature, the key must be in PKCS#8 format
+    /// (`BEGIN PRIVATE KEY`); otherwise, RSA and EC keys are also supported.
+    pub fn from_pem(cert_pem: &[u8], key_pem: &[u8]) -> Result<Self> {
+        let certs = Certificate::from_pem(cert_pem)?.der;
+
+        let key = read_pem(key_pem)?
+            .into_iter()
+            .find_map(|item| match item {
+                Item::PKCS8Key(der) => Some(Key::Pkcs8(der)),
+                Item::RSAKey(der) => Some(Key::Rsa(der)),
+                Item::ECKey(der) => Some(Key::Ec(der)),
+                _ => None,
+            })
+            .ok_or_else(|| tls_error("no private key found in PEM"))?;
+
+        let identity = Self { certs, key };
+
+        // Check the key is supported up front, rather than when the HTTPS
+        // client is built.
+        #[cfg(feature = "rust-tls")]
+        rustls::sign::any_supported_type(&identity.rustls_key())
+            .map_err(|e| tls_error(e.to_string()))?;
+
+        #[cfg(not(feature = "rust-tls"))]
+        identity.native_tls_identity()?;
+
+        Ok(identity)
+    }
+
+    #[cfg(feature = "rust-tls")]
+    fn rustls_key(&self) -> rustls::PrivateKey {
+        rustls::PrivateKey(self.key.der().to_vec())
+    }
+
+    #[cfg(not(feature = "rust-tls"))]
+    fn native_tls_identity(&self) -> Result<native_tls::Identity> {
+        if !matches!(self.key, Key::Pkcs8(_)) {
+            return Err(tls_error(
+                "the private key must be in PKCS#8 format with `native-tls`",
+            ));
+        }
+
+        let cert_pem = self
+            .certs
+            .iter()
+            .map(|der| to_pem("CERTIFICATE", der))
+            .collect::<String>();
+
+        native_tls::Identity::from_pkcs8(
+            cert_pem.as_bytes(),
+            to_pem("PRIVATE KEY", self.key.der()).as_bytes(),
+        )
+        .map_err(|e| tls_error(e.to_string()))
+    }
+}
+
+impl fmt::Debug for Identity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Identity")
+            .field("certs", &self.certs.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Build the `rustls` configuration, which trusts the platform's native
+/// roots along with any extra `roots`, and presents the `identity` (if any)
+/// to the server.
+#[cfg(feature = "rust-tls")]
+pub(crate) fn rustls_config(
+    roots: &[Certificate],
+    identity: Option<&Identity>,
+) -> Result<rustls::ClientConfig> {
+    let mut store = rustls::RootCertStore::empty();
+
+    let native = rustls_native_certs::load_native_certs()?;
+    store.add_parsable_certificates(&native.into_iter().map(|c| c.0).collect::<Vec<_>>());
+
+    for der in roots.iter().flat_map(|cert| &cert.der) {
+        store
+            .add(&rustls::Certificate(der.clone()))
+            .map_err(|e| tls_error(e.to_string()))?;
+    }
+
+    let builder = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(store);
+
+    match identity {
+        Some(identity) => {
+            let certs = identity.certs.iter().cloned().map(rustls::Certificate);
+            builder
+                .with_client_auth_cert(certs.collect(), identity.rustls_key())
+                .map_err(|e| tls_error(e.to_string()))
+        }
+        None => Ok(builder.with_no_client_auth()),
+    }
+}
+
+/// Build the `native-tls` connector, which trusts any extra `roots` along
+/// with the platform's roots, and presents the `identity` (if any) to the
+/// server.
+#[cfg(not(feature = "rust-tls"))]
+pub(crate) fn native_tls_connector(
+    roots: &[Certificate],
+    identity: Option<&Identity>,
+) -> Result<native_tls::TlsConnector> {
+    let mut builder = native_tls::TlsConnector::builder();
+
+    for der in roots.iter().flat_map(|cert| &cert.der) {
+        let cert = native_tls::Certificate::from_der(der).map_err(|e| tls_error(e.to_string()))?;
+        builder.add_root_certificate(cert);
+    }
+
+    if let Some(identity) = identity {
+        builder.identity(identity.native_tls_identity()?);
+    }
+
+    builder.build().map_err(|e| tls_error(e.to_string()))
+}
+
+fn read_pem(pem: &[u8]) -> Result<Vec<Item>> {
+    rustls_pemfile::read_all(&mut BufReader::new(pem)).map_err(|e| tls_error(e.to_string()))
+}
+
+#[cfg(not(feature = "rust-tls"))]
+fn to_pem(label: &str, der: &[u8]) -> String {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+
+    let encoded = STANDARD.encode(der);
+    let mut pem = format!("-----BEGIN {label}-----\n");
+    for line in encoded.as_bytes().chunks(64) {
+        pem.push_str(std::str::from_utf8(line).unwrap());
+        pem.push('\n');
+    }
+    pem.push_str(&format!("-----END {label}-----\n"));
+
+    pem
+}
+
+fn tls_error(reason: impl Into<String>) -> RustWistiaError {
+    RustWistiaError::Tls(reason.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_pem() {
+        let err = Certificate::from_pem(b"not a certificate").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid TLS configuration: no certificate found in PEM"
+        );
+
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_owned()]).unwrap();
+        let cert_pem = cert.serialize_pem().unwrap();
+
+        assert!(Certificate::from_pem(cert_pem.as_bytes()).is_ok());
+        assert!(Identity::from_pem(cert_pem.as_bytes(), cert_pem.as_bytes()).is_err());
+        assert!(Identity::from_pem(
+            cert_pem.as_bytes(),
+            cert.serialize_private_key_pem().as_bytes()
+        )
+        .is_ok());
+    }
+}
@@ -1,79 +1,218 @@
-use crate::auth::auth_token;
-use crate::constants::ENV_VAR_NAME;
-use crate::https::{get_https_client, tls};
+use crate::api::builder::{ClientBuilder, RequestOptions};
+use crate::api::retry::{Replay, Retry};
+use crate::api::span::CallSpan;
+use crate::api::transport::{body_error, BoxTransport, StreamResponse, Transport};
+use crate::auth::{AccessToken, TokenProvider};
+use crate::constants::DATA_API;
+use crate::https::get_https_client;
 use crate::log::*;
 use crate::models::*;
-use crate::status::raise_for_status;
-use crate::utils::{into_struct_from_slice, stream_reader_from_url};
+use crate::status::check_status;
+use crate::utils::{
+    encode_path_segment, into_raw_response, into_struct_from_slice, validate_base_url, REDACTED,
+};
 use crate::RustWistiaError;
 
 use std::borrow::Cow;
+use std::fmt;
 use std::io::Cursor;
-use std::time::Instant;
+use std::sync::Arc;
 
-use hyper::client::{Client, HttpConnector};
-use hyper::header::AUTHORIZATION;
-use hyper::{Body, Method, Request};
+use hyper::body::{Bytes, HttpBody};
+use hyper::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use hyper::{Body, Method, Request, Response};
 use serde::de::DeserializeOwned;
 use serde::ser::Serialize;
-use serde_json::to_vec;
-use serde_urlencoded::to_string;
-
-pub type WistiaClient<'a> = DataClient<'a>;
-
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+pub type WistiaClient = DataClient;
+
+/// The name of the API in the span for each call.
+const DATA_SPAN: &str = "data";
+
+/// Client used to make requests to the Wistia **[Data API]**.
+///
+/// The client owns all of its state, so it is `Clone + Send + Sync + 'static`;
+/// clones share the same underlying connection pool. This makes it easy to
+/// share the client across spawned tasks, or to store it in an `Arc`.
+///
+/// The client is generic over the [`Transport`] which sends requests, which
+/// is hyper by default; see [`DataClient::with_transport`] to use another
+/// transport, such as a [`MockTransport`] in tests.
+///
+/// [Data API]: https://wistia.com/support/developers/data-api
+/// [`MockTransport`]: crate::mock::MockTransport
 #[derive(Clone)]
-pub struct DataClient<'a> {
-    /// Represents the [API access token] used to authenticate requests to the
+pub struct DataClient<T = BoxTransport> {
+    /// Provides the [API access token] used to authenticate requests to the
     /// [Wistia API].
     ///
     /// [API access token]: https://wistia.com/support/developers/data-api#getting-started
     /// [Wistia API]: https://wistia.com/support/developers/upload-api
-    pub access_token: Cow<'a, str>,
-    /// The HTTPS client to use for sending requests.
-    pub client: Client<tls::HttpsConnector<HttpConnector>>,
+    token: Arc<dyn TokenProvider>,
+    /// The base URL of the Wistia Data API.
+    base_url: String,
+    /// The options applied to each request, along with the transport which
+    /// sends it.
+    options: Arc<RequestOptions<T>>,
 }
 
-impl<'a> From<Cow<'a, str>> for DataClient<'a> {
+impl From<AccessToken> for DataClient {
     /// Create a new Wistia `DataClient` from an access token
-    fn from(access_token: Cow<'a, str>) -> Self {
-        let token = auth_token(&access_token);
+    fn from(access_token: AccessToken) -> Self {
+        Self::from_parts(
+            Arc::new(access_token),
+            DATA_API.to_owned(),
+            RequestOptions::new(get_https_client()),
+        )
+    }
+}
 
-        Self {
-            access_token: Cow::Owned(token),
-            client: get_https_client(),
-        }
+impl From<Cow<'_, str>> for DataClient {
+    /// Create a new Wistia `DataClient` from an access token
+    fn from(access_token: Cow<'_, str>) -> Self {
+        Self::from(AccessToken::from(access_token.into_owned()))
     }
 }
 
-impl<'a> From<&'a str> for DataClient<'a> {
+impl From<&str> for DataClient {
     /// Create a new Wistia `DataClient` from an access token
-    fn from(access_token: &'a str) -> Self {
-        Self::from(Cow::Borrowed(access_token))
+    fn from(access_token: &str) -> Self {
+        Self::from(AccessToken::from(access_token))
     }
 }
 
-impl<'a> From<String> for DataClient<'a> {
+impl From<String> for DataClient {
     /// Create a new Wistia `DataClient` from an access token
     fn from(access_token: String) -> Self {
-        Self::from(Cow::Owned(access_token))
+        Self::from(AccessToken::from(access_token))
     }
 }
 
-impl<'a> DataClient<'a> {
+impl DataClient {
     /// Create a new Wistia `DataClient` from an access token
-    pub fn new(access_token: &'a str) -> Self {
-        Self::from(access_token)
+    pub fn new(access_token: impl Into<AccessToken>) -> Self {
+        Self::from(access_token.into())
+    }
+
+    /// Returns a [`ClientBuilder`] to configure a new `DataClient`, such as
+    /// to set timeouts or a custom User-Agent.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
     }
 
     /// Initialize a new Wistia `DataClient` object from an API access token,
     /// assuming this is currently set in the environment.
+    ///
+    /// The base URL of the Data API can also be overridden, via the
+    /// `WISTIA_API_URL` environment variable.
     pub fn from_env() -> crate::Result<Self> {
-        let token: String =
-            std::env::var(ENV_VAR_NAME).map_err(|_| RustWistiaError::EnvVarNotFound {
-                name: ENV_VAR_NAME.to_owned(),
-            })?;
+        ClientBuilder::new().build_data()
+    }
+}
+
+impl<T> fmt::Debug for DataClient<T> {
+    /// The access token is redacted from the output.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DataClient")
+            .field("access_token", &format_args!("{REDACTED}"))
+            .field("base_url", &self.base_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Transport> DataClient<T> {
+    /// Create a new Wistia `DataClient` from an access token, which sends
+    /// requests with a custom `transport`.
+    ///
+    /// The transport is used as-is, so requests are not retried unless it
+    /// is wrapped in a [`RetryLayer`](crate::RetryLayer).
+    pub fn with_transport(access_token: impl Into<AccessToken>, transport: T) -> Self {
+        Self::from_parts(
+            Arc::new(access_token.into()),
+            DATA_API.to_owned(),
+            RequestOptions::with_transport(transport),
+        )
+    }
+
+    /// Create a new Wistia `DataClient` from its parts, so that an existing
+    /// transport (and its connection pool) can be shared.
+    pub(crate) fn from_parts(
+        token: Arc<dyn TokenProvider>,
+        base_url: String,
+        options: RequestOptions<T>,
+    ) -> Self {
+        Self {
+            token,
+            base_url,
+            options: Arc::new(options),
+        }
+    }
+
+    /// Sets the [`TokenProvider`] which is consulted for the access token on
+    /// each call, so that the token can be rotated.
+    pub fn with_token_provider(mut self, provider: impl TokenProvider) -> Self {
+        self.token = Arc::new(provider);
+        self
+    }
+
+    /// Returns a clone of the client which authenticates with another access
+    /// `token`, such as for a call on behalf of another account. The clone
+    /// shares the same connection pool and other options, while any
+    /// [`RateLimit`](crate::RateLimit) applies to the new token on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn example(client: rust_wistia::DataClient) -> rust_wistia::Result<()> {
+    /// let media = client.for_token("tenant-token").get_media("abc1234567").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn for_token(&self, token: impl Into<AccessToken>) -> Self {
+        let token = token.into();
+        Self {
+            options: Arc::new(self.options.for_token(&token)),
+            token: Arc::new(token),
+            base_url: self.base_url.clone(),
+        }
+    }
+
+    /// Returns the current [API access token], from the client's
+    /// [`TokenProvider`].
+    ///
+    /// [API access token]: https://wistia.com/support/developers/data-api#getting-started
+    pub async fn access_token(&self) -> crate::Result<AccessToken> {
+        self.token.token().await
+    }
+
+    /// Returns the transport which sends each request.
+    pub fn transport(&self) -> &T {
+        self.options.transport()
+    }
+
+    /// Sets the base URL of the Wistia Data API (default: `https://api.wistia.com/v1`),
+    /// for example to point the client at a recording proxy or a mock server.
+    ///
+    /// Plain HTTP is only allowed for a local host, such as `http://localhost:8080`.
+    pub fn with_base_url(mut self, base_url: &str) -> crate::Result<Self> {
+        self.base_url = validate_base_url(base_url)?;
+        Ok(self)
+    }
 
-        Ok(Self::from(token))
+    /// Returns the base URL of the Wistia Data API.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Returns the full URL for a `path` on the Wistia Data API, such as
+    /// `medias.json`.
+    pub fn api_url(&self, path: &str) -> String {
+        format!(
+            "{base}/{path}",
+            base = self.base_url,
+            path = path.trim_start_matches('/')
+        )
     }
 
     /// Download an [Asset URL] for a media from Wistia.
@@ -81,16 +220,85 @@ impl<'a> DataClient<'a> {
     /// If `file_path` is specified, the downloaded media content is copied
     /// over to a new file.
     ///
+    /// The content is held in memory in full; to download a large asset,
+    /// use [`DataClient::download_asset_to`] instead.
+    ///
     /// [Asset URL]: https://wistia.com/support/developers/asset-urls
     ///
     /// # Returns
     ///
     /// The bytes content of the source asset for a specified media.
     ///
-    pub async fn download_asset(
-        &'a self,
-        req: DownloadAssetRequest<'a>,
-    ) -> crate::Result<Cursor<hyper::body::Bytes>> {
+    pub async fn download_asset(&self, req: DownloadAssetRequest) -> crate::Result<Cursor<Bytes>> {
+        let file_path = req.file_path.clone();
+
+        // Download the media from the asset url
+        let media_content = self.download_url(&self.asset_source(req).await?).await;
+
+        if let Some(file_path) = file_path {
+            // Copy over media content to a file
+            let mut content = media_content?;
+            let mut file = std::fs::File::create(file_path)?;
+            std::io::copy(&mut content, &mut file)?;
+
+            Ok(content)
+        } else {
+            media_content
+        }
+    }
+
+    /// Download an [Asset URL] for a media from Wistia, and write it to
+    /// `writer` as it is received, rather than holding it in memory. Any
+    /// `file_path` of the request is ignored.
+    ///
+    /// The read timeout applies to each chunk of the asset, and the total
+    /// timeout (if any) to the whole download.
+    ///
+    /// [Asset URL]: https://wistia.com/support/developers/asset-urls
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn example(client: rust_wistia::DataClient) -> rust_wistia::Result<()> {
+    /// use rust_wistia::models::DownloadAssetRequest;
+    ///
+    /// let mut file = Vec::new(); // e.g. a `tokio::fs::File`
+    /// let len = client
+    ///     .download_asset_to(DownloadAssetRequest::from("abc1234567"), &mut file)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_asset_to<W>(
+        &self,
+        req: DownloadAssetRequest,
+        writer: &mut W,
+    ) -> crate::Result<u64>
+    where
+        W: AsyncWrite + Unpin + Send + ?Sized,
+    {
+        let url = self.asset_source(req).await?;
+        let (resp, span) = self.download(&url).await?;
+
+        let mut body = resp.into_body();
+        let mut len = 0;
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk.map_err(|e| span.record_error(body_error(e)))?;
+            writer.write_all(&chunk).await?;
+            len += chunk.len() as u64;
+        }
+        writer.flush().await?;
+        span.record_response_bytes(len);
+
+        Ok(len)
+    }
+
+    /// Returns the URL of the asset to download for a request.
+    async fn asset_source(&self, req: DownloadAssetRequest) -> crate::Result<String> {
         // Use `media` from request, or make a call to the `Medias#Show` API
         // to retrieve asset info for the video.
         let media = if let Some(media) = req.media {
@@ -102,32 +310,49 @@ impl<'a> DataClient<'a> {
         };
 
         // Get the media asset source url (defaults to one for the original file)
-        let url = media.asset_url(req.asset_type)?;
+        let url = media.asset_url(req.asset_type.as_ref().map(AssetType::as_str))?;
 
-        // Download the media from the asset url
-        let media_content = stream_reader_from_url(&url, self.client.clone()).await;
+        Ok(url.to_owned())
+    }
 
-        if let Some(file_path) = req.file_path {
-            // Copy over media content to a file
-            let mut content = media_content?;
-            let mut file = std::fs::File::create(file_path)?;
-            std::io::copy(&mut content, &mut file)?;
+    /// Download the contents of a `url`, with the same options (such as
+    /// timeouts) as requests to the Data API.
+    pub(crate) async fn download_url(&self, url: &str) -> crate::Result<Cursor<Bytes>> {
+        let (resp, span) = self.download(url).await?;
 
-            Ok(content)
-        } else {
-            media_content
-        }
+        let bytes = hyper::body::to_bytes(resp.into_body())
+            .await
+            .map_err(|e| span.record_error(body_error(e)))?;
+        span.record_response_bytes(bytes.len() as u64);
+
+        Ok(Cursor::new(bytes))
+    }
+
+    /// Send a request to download a `url`, whose response body is streamed.
+    async fn download(&self, url: &str) -> crate::Result<(Response<Body>, CallSpan)> {
+        let mut req = Request::get(url).body(Body::empty())?;
+        req.extensions_mut().insert(StreamResponse);
+        let span = CallSpan::new(DATA_SPAN, &req);
+
+        let resp = span
+            .instrument(self.options.send(req, Retry::Idempotent, None))
+            .await
+            .map_err(|e| span.record_error(e))?;
+        span.record_response(&resp);
+
+        Ok((resp, span))
     }
 
     /// Retrieve info on a media on Wistia (typically a video)
     ///
+    /// The `video_id` can also be a Wistia URL or embed code, such as one
+    /// pasted by a user, as parsed by [`EmbedInfo`](crate::models::EmbedInfo).
+    ///
     /// # Docs
     /// <https://wistia.com/support/developers/data-api#medias-show>
-    pub async fn get_media(&self, video_id: &'a str) -> crate::Result<Media> {
-        let url = format!(
-            "https://api.wistia.com/v1/medias/{media_id}.json",
-            media_id = video_id
-        );
+    pub async fn get_media(&self, video_id: impl Into<MediaHashedId>) -> crate::Result<Media> {
+        let id = video_id.into();
+        let url = self.api_url(&format!("medias/{}.json", encode_path_segment(id.as_str())));
 
         self.get(&url).await
     }
@@ -137,88 +362,311 @@ impl<'a> DataClient<'a> {
     /// # Docs
     /// <https://wistia.com/support/developers/data-api#medias-update>
     pub async fn update_media(&self, video: UpdateMediaRequest) -> crate::Result<MediaInfo> {
-        let url = format!(
-            "https://api.wistia.com/v1/medias/{media_id}.json",
-            media_id = video.id
-        );
+        let url = self.api_url(&format!(
+            "medias/{}.json",
+            encode_path_segment(video.id.as_str())
+        ));
 
         self.put(&url, video).await
     }
 
     /// Make a GET request to the Wistia Data API
-    pub async fn get<R: DeserializeOwned>(&'a self, url: &'a str) -> crate::Result<R> {
-        let token = self.access_token.as_ref();
+    pub async fn get<R: DeserializeOwned>(&self, url: &str) -> crate::Result<R> {
+        self.request(Method::GET, url, RequestBody::Empty).await
+    }
+
+    /// Make a POST request to the Wistia Data API, with included *query parameters*
+    pub async fn post<B: Serialize, R: DeserializeOwned>(
+        &self,
+        url: &str,
+        params: B,
+    ) -> crate::Result<R> {
+        self.request(Method::POST, url, RequestBody::query(params)?)
+            .await
+    }
 
-        let req = Request::builder()
-            .method(Method::GET)
-            .uri(url)
-            .header(AUTHORIZATION, token)
-            .body(Body::empty())?;
+    /// Make a POST request to the Wistia Data API, with a JSON body
+    pub async fn post_with_body<B: Serialize, R: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: B,
+    ) -> crate::Result<R> {
+        self.request(Method::POST, url, RequestBody::json(&body)?)
+            .await
+    }
 
-        self.make_request(url, req).await
+    /// Make a POST request to the Wistia Data API, with a *form-url encoded* body
+    pub async fn post_form<B: Serialize, R: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: B,
+    ) -> crate::Result<R> {
+        self.request(Method::POST, url, RequestBody::form(body)?)
+            .await
     }
 
     /// Make a PUT request to the Wistia Data API, with included *query parameters*
     pub async fn put<B: Serialize, R: DeserializeOwned>(
-        &'a self,
-        url: &'a str,
+        &self,
+        url: &str,
         body: B,
     ) -> crate::Result<R> {
-        let token = self.access_token.as_ref();
-
-        let mut uri: String;
-        let params = to_string(body)?;
-        let params_len = params.len();
-
-        let url = if params_len != 0 {
-            uri = String::with_capacity(url.len() + params.len() + 1);
-            uri.push_str(url);
-            uri.push('?');
-            uri.push_str(&params);
-            uri.as_str()
-        } else {
-            url
-        };
-
-        let req = Request::builder()
-            .method(Method::PUT)
-            .uri(url)
-            .header(AUTHORIZATION, token)
-            .body(Body::empty())?;
-
-        self.make_request(url, req).await
+        self.request(Method::PUT, url, RequestBody::query(body)?)
+            .await
     }
 
-    /// Make a PUT request to the Wistia Data API
+    /// Make a PUT request to the Wistia Data API, with a JSON body
     pub async fn put_with_body<B: Serialize, R: DeserializeOwned>(
-        &'a self,
-        url: &'a str,
+        &self,
+        url: &str,
         body: B,
     ) -> crate::Result<R> {
-        let token = self.access_token.as_ref();
-        let body_data = to_vec(&body)?;
+        self.request(Method::PUT, url, RequestBody::json(&body)?)
+            .await
+    }
 
-        let req = Request::builder()
-            .method(Method::PUT)
-            .uri(url)
-            .header(AUTHORIZATION, token)
-            .body(Body::from(body_data))?;
+    /// Make a PUT request to the Wistia Data API, with a *form-url encoded* body
+    pub async fn put_form<B: Serialize, R: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: B,
+    ) -> crate::Result<R> {
+        self.request(Method::PUT, url, RequestBody::form(body)?)
+            .await
+    }
 
-        self.make_request(url, req).await
+    /// Make a PATCH request to the Wistia Data API, with included *query parameters*
+    pub async fn patch<B: Serialize, R: DeserializeOwned>(
+        &self,
+        url: &str,
+        params: B,
+    ) -> crate::Result<R> {
+        self.request(Method::PATCH, url, RequestBody::query(params)?)
+            .await
     }
 
-    /// Send the request to the Wistia Data API
-    pub(crate) async fn make_request<R: DeserializeOwned>(
-        &'a self,
-        url: &'a str,
-        req: Request<Body>,
+    /// Make a PATCH request to the Wistia Data API, with a JSON body
+    pub async fn patch_with_body<B: Serialize, R: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: B,
     ) -> crate::Result<R> {
-        let start = Instant::now();
-        let mut resp = self.client.request(req).await?;
-        debug!("Call Data API completed {:.2?}", start.elapsed());
+        self.request(Method::PATCH, url, RequestBody::json(&body)?)
+            .await
+    }
 
-        raise_for_status(url, &mut resp).await?;
+    /// Make a PATCH request to the Wistia Data API, with a *form-url encoded* body
+    pub async fn patch_form<B: Serialize, R: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: B,
+    ) -> crate::Result<R> {
+        self.request(Method::PATCH, url, RequestBody::form(body)?)
+            .await
+    }
+
+    /// Make a DELETE request to the Wistia Data API
+    ///
+    /// # Note
+    /// If the response has an empty body, it is de-serialized as JSON `null`,
+    /// so `R` can be `()` or an `Option`.
+    pub async fn delete<R: DeserializeOwned>(&self, url: &str) -> crate::Result<R> {
+        self.request(Method::DELETE, url, RequestBody::Empty).await
+    }
+
+    /// Make a request to the Wistia Data API, with the specified HTTP
+    /// `method` and request `body`.
+    pub async fn request<R: DeserializeOwned>(
+        &self,
+        method: Method,
+        url: &str,
+        body: RequestBody,
+    ) -> crate::Result<R> {
+        let resp = self.send_request(method, url, body).await?;
 
         into_struct_from_slice(resp).await
     }
+
+    /// Make a GET request to the Wistia Data API, and return the *raw* response
+    pub async fn get_raw(&self, url: &str) -> crate::Result<RawResponse> {
+        self.request_raw(Method::GET, url, RequestBody::Empty).await
+    }
+
+    /// Make a POST request to the Wistia Data API, and return the *raw* response
+    pub async fn post_raw(&self, url: &str, body: RequestBody) -> crate::Result<RawResponse> {
+        self.request_raw(Method::POST, url, body).await
+    }
+
+    /// Make a PUT request to the Wistia Data API, and return the *raw* response
+    pub async fn put_raw(&self, url: &str, body: RequestBody) -> crate::Result<RawResponse> {
+        self.request_raw(Method::PUT, url, body).await
+    }
+
+    /// Make a PATCH request to the Wistia Data API, and return the *raw* response
+    pub async fn patch_raw(&self, url: &str, body: RequestBody) -> crate::Result<RawResponse> {
+        self.request_raw(Method::PATCH, url, body).await
+    }
+
+    /// Make a DELETE request to the Wistia Data API, and return the *raw* response
+    pub async fn delete_raw(&self, url: &str) -> crate::Result<RawResponse> {
+        self.request_raw(Method::DELETE, url, RequestBody::Empty)
+            .await
+    }
+
+    /// Make a request to the Wistia Data API, with the specified HTTP
+    /// `method` and request `body`, and return the *raw* response.
+    ///
+    /// This can be used to call endpoints which are not (yet) wrapped by
+    /// this library, with the same authentication and error handling.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use hyper::Method;
+    /// use rust_wistia::models::RequestBody;
+    /// use rust_wistia::WistiaClient;
+    ///
+    /// let client = WistiaClient::from_env()?;
+    /// let url = client.api_url("account.json");
+    /// let res = client.request_raw(Method::GET, &url, RequestBody::Empty).await?;
+    ///
+    /// if let Some(body) = res.body {
+    ///     println!("{}: {}", res.status, body["name"]);
+    /// }
+    /// ```
+    pub async fn request_raw(
+        &self,
+        method: Method,
+        url: &str,
+        body: RequestBody,
+    ) -> crate::Result<RawResponse> {
+        let resp = self.send_request(method, url, body).await?;
+
+        into_raw_response(resp).await
+    }
+
+    /// Build a request to the Wistia Data API, returning the request URL
+    /// (with any *query parameters* included) along with the request.
+    ///
+    /// The request is authenticated with `token`, the value of the
+    /// `AUTHORIZATION` header.
+    fn build_request(
+        token: &HeaderValue,
+        method: Method,
+        url: &str,
+        body: RequestBody,
+    ) -> crate::Result<(String, Request<Body>)> {
+        let content_type = body.content_type();
+
+        let (url, body) = match body {
+            RequestBody::Empty => (url.to_owned(), Body::empty()),
+            RequestBody::Query(params) if params.is_empty() => (url.to_owned(), Body::empty()),
+            RequestBody::Query(params) => {
+                let sep = if url.contains('?') { '&' } else { '?' };
+                (format!("{url}{sep}{params}"), Body::empty())
+            }
+            RequestBody::Form(data) => (url.to_owned(), Body::from(data)),
+            RequestBody::Json(data) => (url.to_owned(), Body::from(data)),
+        };
+
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(&url)
+            .header(AUTHORIZATION, token);
+
+        if let Some(content_type) = content_type {
+            builder = builder.header(CONTENT_TYPE, content_type);
+        }
+
+        let req = builder.body(body)?;
+
+        Ok((url, req))
+    }
+
+    /// Send a request to the Wistia Data API, and check the status of the
+    /// response.
+    ///
+    /// The request is re-built for each attempt, if it is retried.
+    async fn send_request(
+        &self,
+        method: Method,
+        url: &str,
+        body: RequestBody,
+    ) -> crate::Result<Response<Body>> {
+        // The token is resolved once per call, rather than for each retry.
+        let token = self.access_token().await?;
+        let auth = token.bearer_header()?;
+
+        let (req_url, req) = Self::build_request(&auth, method.clone(), url, body.clone())?;
+
+        let url = url.to_owned();
+        let req_method = method.clone();
+        let replay = Replay::new(move || {
+            let (_, req) = Self::build_request(&auth, req_method.clone(), &url, body.clone())?;
+            Ok(req)
+        });
+
+        let span = CallSpan::new(DATA_SPAN, &req);
+        let mut resp = span
+            .instrument(self.options.send(req, Retry::Idempotent, Some(replay)))
+            .await
+            .map_err(|e| span.record_error(e))?;
+        span.record_response(&resp);
+        debug!("Call Data API completed {:.2?}", span.elapsed());
+
+        // The access token is sent in a header (rather than the URL), so it
+        // is redacted from the response explicitly.
+        check_status(Some(&method), &req_url, &mut resp, &[token.expose_secret()])
+            .await
+            .map_err(|e| span.record_error(e))?;
+
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_request() {
+        let token = AccessToken::new("my-token").bearer_header().unwrap();
+        let url = "https://api.wistia.com/v1/medias/abc1234567.json";
+
+        let body = RequestBody::query([("name", "My Video")]).unwrap();
+        let (req_url, req) = <DataClient>::build_request(&token, Method::PUT, url, body).unwrap();
+
+        assert_eq!(req_url, format!("{url}?name=My+Video"));
+        assert_eq!(req.headers()[AUTHORIZATION], "Bearer my-token");
+        assert!(req.headers().get(CONTENT_TYPE).is_none());
+
+        let body = RequestBody::json(&serde_json::json!({"name": "My Video"})).unwrap();
+        let (req_url, req) = <DataClient>::build_request(&token, Method::PATCH, url, body).unwrap();
+
+        assert_eq!(req_url, url);
+        assert_eq!(req.method(), Method::PATCH);
+        assert_eq!(req.headers()[CONTENT_TYPE], "application/json");
+    }
+
+    fn assert_client<T: Clone + Send + Sync + 'static>() {}
+
+    fn assert_send<T: Send>(_: &T) {}
+
+    #[test]
+    fn test_client_is_send_sync_static() {
+        assert_client::<DataClient>();
+
+        // The returned futures are `Send`, and arguments can be borrowed
+        // for a shorter lifetime than the client.
+        let client = DataClient::from("my-token");
+        let url = client.api_url("medias.json");
+
+        assert_send(&client.get_media("abc1234567"));
+        assert_send(&client.update_media(UpdateMediaRequest::new("abc1234567")));
+        assert_send(&client.download_asset("abc1234567".into()));
+        assert_send(&client.get::<Vec<Media>>(&url));
+        assert_send(&client.put::<_, MediaInfo>(&url, [("name", "My Video")]));
+        assert_send(&client.delete::<()>(&url));
+        assert_send(&client.request_raw(Method::GET, &url, RequestBody::Empty));
+    }
 }
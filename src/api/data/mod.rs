@@ -1,3 +1,5 @@
 mod client;
+mod stats;
 
 pub use client::*;
+pub use stats::*;
@@ -0,0 +1,83 @@
+use crate::api::data::DataClient;
+use crate::api::transport::{BoxTransport, Transport};
+use crate::models::*;
+use crate::utils::encode_path_segment;
+
+use std::fmt;
+
+/// Client used to make requests to the Wistia **[Stats API]**, which
+/// returns aggregated tracking statistics for the account, its projects
+/// and its medias.
+///
+/// [Stats API]: https://wistia.com/support/developers/stats-api
+///
+#[derive(Clone)]
+pub struct StatsClient<T = BoxTransport> {
+    client: DataClient<T>,
+}
+
+impl<T> From<DataClient<T>> for StatsClient<T> {
+    /// Create a new `StatsClient` which uses a `DataClient` to send requests
+    fn from(client: DataClient<T>) -> Self {
+        Self { client }
+    }
+}
+
+impl StatsClient {
+    /// Initialize a new `StatsClient` object from an API access token,
+    /// assuming this is currently set in the environment.
+    pub fn from_env() -> crate::Result<Self> {
+        DataClient::from_env().map(Self::from)
+    }
+}
+
+impl<T> fmt::Debug for StatsClient<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StatsClient")
+            .field("client", &self.client)
+            .finish()
+    }
+}
+
+impl<T: Transport> StatsClient<T> {
+    /// Retrieve the aggregated statistics for the account.
+    ///
+    /// # Docs
+    /// <https://wistia.com/support/developers/stats-api#account>
+    pub async fn account(&self) -> crate::Result<AccountStats> {
+        let url = self.client.api_url("stats/account.json");
+
+        self.client.get(&url).await
+    }
+
+    /// Retrieve the aggregated statistics for a project.
+    ///
+    /// # Docs
+    /// <https://wistia.com/support/developers/stats-api#projects>
+    pub async fn project(
+        &self,
+        project_id: impl Into<ProjectHashedId>,
+    ) -> crate::Result<ProjectStats> {
+        let id = project_id.into();
+        let url = self.client.api_url(&format!(
+            "stats/projects/{}.json",
+            encode_path_segment(id.as_str())
+        ));
+
+        self.client.get(&url).await
+    }
+
+    /// Retrieve the aggregated statistics for a media (typically a video).
+    ///
+    /// # Docs
+    /// <https://wistia.com/support/developers/stats-api#medias>
+    pub async fn media(&self, media_id: impl Into<MediaHashedId>) -> crate::Result<MediaStats> {
+        let id = media_id.into();
+        let url = self.client.api_url(&format!(
+            "stats/medias/{}.json",
+            encode_path_segment(id.as_str())
+        ));
+
+        self.client.get(&url).await
+    }
+}
@@ -1,7 +1,23 @@
-#[cfg(feature = "upload-url")]
+mod builder;
+mod certs;
 mod data;
+mod proxy;
+mod rate_limit;
+mod registry;
+mod retry;
+mod span;
+mod transport;
 mod upload;
+mod wistia;
 
-#[cfg(feature = "upload-url")]
+pub use builder::{ClientBuilder, HttpVersion};
+pub use certs::{Certificate, Identity};
 pub use data::*;
+pub use proxy::{Proxy, ProxyConnector};
+pub use rate_limit::{RateLimit, RateLimitLayer, RateLimitService};
+pub use registry::{ClientRegistry, TenantMetrics};
+pub(crate) use retry::{is_retryable_status, retry_after};
+pub use retry::{RetryLayer, RetryPolicy, RetryService};
+pub use transport::{BoxError, BoxTransport, HttpsTransport, ResponseFuture, Transport};
 pub use upload::*;
+pub use wistia::*;
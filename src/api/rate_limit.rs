@@ -0,0 +1,332 @@
+//! Client-side rate limiting, so that concurrent requests queue locally
+//! rather than exceed the request quota of a Wistia account.
+//!
+use crate::auth::AccessToken;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use hyper::Request;
+use tokio::time::Instant;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// A limit on the rate of requests, which is enforced by a *token bucket*.
+///
+/// The bucket holds up to `burst` tokens, and is refilled at the rate of
+/// `requests_per_minute`. Each request (including each retry) takes a
+/// token, and waits for the bucket to refill if it is empty.
+///
+/// The limiter is shared by all clients which use the same access token,
+/// including a clone returned by `for_token()`; if these clients are built
+/// with a different `RateLimit`, the first one is used. With a
+/// [`TokenProvider`], it is shared by the clones of a client instead.
+///
+/// [`TokenProvider`]: crate::TokenProvider
+///
+/// # Examples
+///
+/// ```
+/// use rust_wistia::{ClientBuilder, RateLimit};
+///
+/// // Wistia allows 600 requests per minute for an account
+/// let builder = ClientBuilder::new().rate_limit(RateLimit::per_minute(600));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    requests_per_minute: u32,
+    burst: u32,
+}
+
+impl RateLimit {
+    /// A limit of `requests_per_minute`, which allows a burst of up to one
+    /// second's worth of requests.
+    pub fn per_minute(requests_per_minute: u32) -> Self {
+        let requests_per_minute = requests_per_minute.max(1);
+
+        Self {
+            requests_per_minute,
+            burst: (requests_per_minute / 60).max(1),
+        }
+    }
+
+    /// Sets the maximum number of requests which can be sent at once, after
+    /// the client has been idle.
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+
+    /// Returns the number of tokens added to the bucket per second.
+    fn per_second(&self) -> f64 {
+        f64::from(self.requests_per_minute) / 60.0
+    }
+}
+
+/// A token bucket, which enforces a [`RateLimit`].
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    limit: RateLimit,
+    state: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// The tokens in the bucket, which is negative when requests are queued.
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            state: Mutex::new(Bucket {
+                tokens: f64::from(limit.burst),
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Returns the rate limiter shared by all clients with the access
+    /// `token`, creating one with `limit` if there is none.
+    pub(crate) fn shared(token: &AccessToken, limit: RateLimit) -> Arc<Self> {
+        static LIMITERS: OnceLock<Mutex<HashMap<TokenKey, Weak<RateLimiter>>>> = OnceLock::new();
+
+        let mut limiters = LIMITERS.get_or_init(Default::default).lock().unwrap();
+        let key = TokenKey(token.clone());
+
+        if let Some(limiter) = limiters.get(&key).and_then(Weak::upgrade) {
+            return limiter;
+        }
+
+        // The token is zeroized once its entry is removed, along with the
+        // last client which uses it.
+        limiters.retain(|_, limiter| limiter.strong_count() > 0);
+
+        let limiter = Arc::new(Self::new(limit));
+        limiters.insert(key, Arc::downgrade(&limiter));
+
+        limiter
+    }
+
+    /// Wait until a request can be sent.
+    ///
+    /// A token is reserved right away, so that waiting requests are sent in
+    /// the order that they arrived. If the future is dropped before the wait
+    /// is over (such as on a timeout), the token is returned to the bucket.
+    pub(crate) async fn acquire(&self) {
+        let mut reservation = Reservation {
+            limiter: self,
+            done: false,
+        };
+
+        let wait = {
+            let mut bucket = self.state.lock().unwrap();
+            let now = Instant::now();
+
+            let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * self.limit.per_second())
+                .min(f64::from(self.limit.burst));
+            bucket.last_refill = now;
+
+            bucket.tokens -= 1.0;
+
+            if bucket.tokens >= 0.0 {
+                Duration::ZERO
+            } else {
+                Duration::from_secs_f64(-bucket.tokens / self.limit.per_second())
+            }
+        };
+
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+
+        reservation.done = true;
+    }
+
+    /// Return a token which was reserved, but not used.
+    fn release(&self) {
+        let mut bucket = self.state.lock().unwrap();
+        bucket.tokens = (bucket.tokens + 1.0).min(f64::from(self.limit.burst));
+    }
+}
+
+/// The key of a shared [`RateLimiter`], which is the access token itself so
+/// that two tokens can never share a limiter.
+struct TokenKey(AccessToken);
+
+impl PartialEq for TokenKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.expose_secret() == other.0.expose_secret()
+    }
+}
+
+impl Eq for TokenKey {}
+
+impl Hash for TokenKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.expose_secret().hash(state);
+    }
+}
+
+/// The rate limiter of the client which sends a request, which is set as
+/// a request extension so that each clone from `for_token()` can wait on
+/// the limiter of its own token.
+#[derive(Debug, Clone)]
+pub(crate) struct RequestLimiter(pub(crate) Arc<RateLimiter>);
+
+/// A token reserved by [`RateLimiter::acquire`], which is returned to the
+/// bucket if it is dropped before the wait is over.
+struct Reservation<'a> {
+    limiter: &'a RateLimiter,
+    done: bool,
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.limiter.release();
+        }
+    }
+}
+
+/// A [`Layer`] which waits for each request to be allowed by a
+/// [`RateLimit`], before it is sent.
+///
+/// All clones of the layer, and all services it wraps, share the same rate
+/// limit. The clients built by a [`ClientBuilder`] with
+/// [`ClientBuilder::rate_limit`] already apply a limit, shared by all
+/// clients with the same access token.
+///
+/// [`ClientBuilder`]: crate::ClientBuilder
+/// [`ClientBuilder::rate_limit`]: crate::ClientBuilder::rate_limit
+#[derive(Debug, Clone)]
+pub struct RateLimitLayer {
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl RateLimitLayer {
+    /// Create a new `RateLimitLayer` with a rate limit.
+    pub fn new(limit: RateLimit) -> Self {
+        Self::from_limiter(Arc::new(RateLimiter::new(limit)))
+    }
+
+    pub(crate) fn from_limiter(limiter: Arc<RateLimiter>) -> Self {
+        Self {
+            limiter: Some(limiter),
+        }
+    }
+
+    /// A layer which waits on the [`RequestLimiter`] of each request, if
+    /// any, rather than on a limiter of its own.
+    pub(crate) fn per_request() -> Self {
+        Self { limiter: None }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+/// A [`Service`] which waits for each request to be allowed by a
+/// [`RateLimit`], before it is sent.
+#[derive(Debug, Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl<S, B> Service<Request<B>> for RateLimitService<S>
+where
+    S: Service<Request<B>> + Clone + Send + 'static,
+    S::Future: Send,
+    B: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let limiter = self.limiter.clone().or_else(|| {
+            req.extensions()
+                .get::<RequestLimiter>()
+                .map(|limiter| limiter.0.clone())
+        });
+
+        Box::pin(async move {
+            if let Some(limiter) = limiter {
+                limiter.acquire().await;
+            }
+            inner.call(req).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_acquire() {
+        let limiter = RateLimiter::new(RateLimit::per_minute(60).burst(2));
+        let start = Instant::now();
+
+        // The burst is sent right away
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        // Then one request per second
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert_eq!(start.elapsed().as_secs(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_acquire_cancelled() {
+        let limiter = RateLimiter::new(RateLimit::per_minute(60).burst(1));
+        let start = Instant::now();
+
+        limiter.acquire().await;
+
+        // A request which times out while waiting gives back its token
+        let cancelled = tokio::time::timeout(Duration::from_millis(100), limiter.acquire());
+        assert!(cancelled.await.is_err());
+
+        // So the next request only waits for the first one
+        limiter.acquire().await;
+        assert!(start.elapsed() < Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn test_shared_by_token() {
+        let limit = RateLimit::per_minute(60);
+        let token = AccessToken::from("shared-token");
+
+        let limiter = RateLimiter::shared(&token, limit);
+        assert!(Arc::ptr_eq(&limiter, &RateLimiter::shared(&token, limit)));
+
+        let other = RateLimiter::shared(&AccessToken::from("other-token"), limit);
+        assert!(!Arc::ptr_eq(&limiter, &other));
+    }
+}
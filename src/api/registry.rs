@@ -0,0 +1,348 @@
+//! A registry of clients for many Wistia accounts (*tenants*), which share
+//! one connection pool and rate limit.
+//!
+use crate::api::builder::{ClientBuilder, RequestOptions};
+use crate::api::client::UploadClient;
+use crate::api::data::DataClient;
+use crate::api::rate_limit::RateLimiter;
+use crate::api::transport::{BoxTransport, HttpsTransport, ResponseFuture};
+use crate::api::wistia::Wistia;
+use crate::auth::TokenProvider;
+use crate::types::Result;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use hyper::header::HeaderMap;
+use hyper::{Body, Request, Response, StatusCode};
+use tower_service::Service;
+
+/// Resolves the access token of a tenant.
+type Resolver = dyn Fn(&str) -> Result<Arc<dyn TokenProvider>> + Send + Sync;
+
+/// A registry of clients keyed by *tenant* ID, for an application which
+/// calls the Wistia API on behalf of many accounts.
+///
+/// The client for a tenant is built the first time it is requested, with
+/// the access token (or [`TokenProvider`]) returned by the registry's
+/// resolver, and is then cached. All clients share one connection pool,
+/// along with the [rate limit](ClientBuilder::rate_limit) of the builder;
+/// each tenant can additionally be limited via
+/// [`ClientBuilder::tenant_rate_limit`].
+///
+/// Cloning a `ClientRegistry` is cheap, and the clones share the same
+/// clients.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use rust_wistia::{AccessToken, ClientRegistry, RateLimit, RustWistiaError};
+///
+/// let registry = ClientRegistry::builder()
+///     .rate_limit(RateLimit::per_minute(6000))
+///     .tenant_rate_limit(RateLimit::per_minute(600))
+///     .build_registry(|tenant| match lookup_token(tenant) {
+///         Some(token) => Ok(AccessToken::from(token)),
+///         None => Err(RustWistiaError::UnknownTenant {
+///             tenant: tenant.to_owned(),
+///         }),
+///     })?;
+///
+/// let media = registry.get("tenant-1")?.get_media("abc1234567").await?;
+///
+/// let metrics = registry.metrics("tenant-1").unwrap();
+/// println!("requests: {}, errors: {}", metrics.requests, metrics.errors);
+/// ```
+#[derive(Clone)]
+pub struct ClientRegistry {
+    inner: Arc<Registry>,
+}
+
+struct Registry {
+    builder: ClientBuilder,
+    headers: HeaderMap,
+    base_url: String,
+    upload_url: String,
+    /// The transport shared by all tenants, and so its connection pool.
+    https: HttpsTransport,
+    /// The rate limiter shared by all tenants, if any.
+    limiter: Option<Arc<RateLimiter>>,
+    resolver: Box<Resolver>,
+    tenants: RwLock<HashMap<String, Tenant>>,
+}
+
+/// The (cached) client for a tenant.
+#[derive(Clone)]
+struct Tenant {
+    client: Wistia,
+    metrics: Arc<Metrics>,
+}
+
+impl ClientRegistry {
+    /// Returns a [`ClientBuilder`] to configure the clients of a new
+    /// `ClientRegistry`, which is then built via
+    /// [`ClientBuilder::build_registry`].
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// Create a new `ClientRegistry` from the `builder`, which resolves the
+    /// access token of each tenant via `resolver`.
+    pub(crate) fn from_builder<F, P>(builder: ClientBuilder, resolver: F) -> Result<Self>
+    where
+        F: Fn(&str) -> Result<P> + Send + Sync + 'static,
+        P: TokenProvider,
+    {
+        let resolver = move |tenant: &str| -> Result<Arc<dyn TokenProvider>> {
+            Ok(Arc::new(resolver(tenant)?))
+        };
+
+        Ok(Self {
+            inner: Arc::new(Registry {
+                headers: builder.default_headers()?,
+                base_url: builder.resolve_base_url()?,
+                upload_url: builder.resolve_upload_url()?,
+                https: builder.https_transport()?,
+                limiter: builder
+                    .rate_limit
+                    .map(|limit| Arc::new(RateLimiter::new(limit))),
+                resolver: Box::new(resolver),
+                tenants: RwLock::default(),
+                builder,
+            }),
+        })
+    }
+
+    /// Returns the client for the Wistia [Data API] of a `tenant`, building
+    /// it if needed.
+    ///
+    /// [Data API]: https://wistia.com/support/developers/data-api
+    pub fn get(&self, tenant: &str) -> Result<DataClient> {
+        Ok(self.tenant(tenant)?.client.data().clone())
+    }
+
+    /// Returns the client for the Wistia [Upload API] of a `tenant`,
+    /// building it if needed.
+    ///
+    /// [Upload API]: https://wistia.com/support/developers/upload-api
+    pub fn upload(&self, tenant: &str) -> Result<UploadClient> {
+        Ok(self.tenant(tenant)?.client.upload().clone())
+    }
+
+    /// Returns the [`Wistia`] client of a `tenant`, building it if needed.
+    pub fn wistia(&self, tenant: &str) -> Result<Wistia> {
+        Ok(self.tenant(tenant)?.client)
+    }
+
+    /// Returns the metrics of the requests sent for a `tenant`, or `None`
+    /// if its client has not been built.
+    pub fn metrics(&self, tenant: &str) -> Option<TenantMetrics> {
+        let tenants = self.inner.tenants.read().unwrap();
+
+        tenants.get(tenant).map(|tenant| tenant.metrics.snapshot())
+    }
+
+    /// Returns the IDs of the tenants whose clients have been built.
+    pub fn tenants(&self) -> Vec<String> {
+        self.inner.tenants.read().unwrap().keys().cloned().collect()
+    }
+
+    /// Removes the cached client of a `tenant`, such as when its access
+    /// token is revoked, so that it is built again on the next request.
+    ///
+    /// Returns `true` if the client had been built.
+    pub fn remove(&self, tenant: &str) -> bool {
+        self.inner.tenants.write().unwrap().remove(tenant).is_some()
+    }
+
+    /// Returns the (cached) client of a `tenant`, building it if needed.
+    fn tenant(&self, tenant: &str) -> Result<Tenant> {
+        if let Some(cached) = self.inner.tenants.read().unwrap().get(tenant) {
+            return Ok(cached.clone());
+        }
+
+        let mut tenants = self.inner.tenants.write().unwrap();
+
+        // Another thread may have built the client in the meantime
+        if let Some(cached) = tenants.get(tenant) {
+            return Ok(cached.clone());
+        }
+
+        let built = self.inner.build(tenant)?;
+        tenants.insert(tenant.to_owned(), built.clone());
+
+        Ok(built)
+    }
+}
+
+impl Registry {
+    /// Build the client of a `tenant`, which shares the connection pool and
+    /// rate limit of the registry.
+    fn build(&self, tenant: &str) -> Result<Tenant> {
+        let token = (self.resolver)(tenant)?;
+        let metrics = Arc::new(Metrics::default());
+
+        let https = BoxTransport::new(MetricsService {
+            inner: self.https.clone(),
+            metrics: metrics.clone(),
+        });
+
+        // The tenant's limit is waited on first, so that one tenant does not
+        // hold up the others while it is over its own limit.
+        let tenant_limiter = self
+            .builder
+            .tenant_rate_limit
+            .map(|limit| Arc::new(RateLimiter::new(limit)));
+        let limiters = self.limiter.iter().cloned().chain(tenant_limiter);
+
+        let transport = self.builder.wrap_transport(https, limiters);
+        let options = RequestOptions::from_parts(self.headers.clone(), transport);
+
+        Ok(Tenant {
+            client: Wistia::from_clients(
+                DataClient::from_parts(token.clone(), self.base_url.clone(), options.clone()),
+                UploadClient::from_parts(token, self.upload_url.clone(), options)
+                    .with_auth(self.builder.upload_auth),
+            ),
+            metrics,
+        })
+    }
+}
+
+impl fmt::Debug for ClientRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientRegistry")
+            .field("base_url", &self.inner.base_url)
+            .field("upload_url", &self.inner.upload_url)
+            .field("tenants", &self.tenants())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A snapshot of the requests sent for a tenant of a [`ClientRegistry`].
+///
+/// Each attempt of a request is counted, so a request which is retried is
+/// counted more than once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TenantMetrics {
+    /// The number of requests sent.
+    pub requests: u64,
+    /// The number of requests which failed, with an error status (`4xx`
+    /// or `5xx`), a timeout or a connection error.
+    pub errors: u64,
+    /// The number of requests which were rate limited by Wistia, that is
+    /// with a `429 Too Many Requests` status.
+    pub rate_limited: u64,
+    /// The total time spent waiting on responses.
+    pub latency: Duration,
+}
+
+impl TenantMetrics {
+    /// Returns the average time spent waiting on a response, if any
+    /// requests were sent.
+    pub fn average_latency(&self) -> Option<Duration> {
+        u32::try_from(self.requests)
+            .ok()
+            .filter(|&requests| requests > 0)
+            .map(|requests| self.latency / requests)
+    }
+}
+
+/// The counters behind [`TenantMetrics`].
+#[derive(Debug, Default)]
+struct Metrics {
+    requests: AtomicU64,
+    errors: AtomicU64,
+    rate_limited: AtomicU64,
+    latency_micros: AtomicU64,
+}
+
+impl Metrics {
+    fn record(&self, status: Option<StatusCode>, latency: Duration) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.latency_micros
+            .fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+
+        match status {
+            Some(StatusCode::TOO_MANY_REQUESTS) => {
+                self.rate_limited.fetch_add(1, Ordering::Relaxed);
+                self.errors.fetch_add(1, Ordering::Relaxed);
+            }
+            Some(status) if !status.is_client_error() && !status.is_server_error() => {}
+            _ => {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn snapshot(&self) -> TenantMetrics {
+        TenantMetrics {
+            requests: self.requests.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            rate_limited: self.rate_limited.load(Ordering::Relaxed),
+            latency: Duration::from_micros(self.latency_micros.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// A [`Service`] which records the metrics of each request it sends.
+#[derive(Clone)]
+struct MetricsService {
+    inner: HttpsTransport,
+    metrics: Arc<Metrics>,
+}
+
+impl Service<Request<Body>> for MetricsService {
+    type Response = Response<Body>;
+    type Error = crate::RustWistiaError;
+    type Future = ResponseFuture;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let fut = self.inner.call(req);
+        let metrics = self.metrics.clone();
+
+        Box::pin(async move {
+            let start = Instant::now();
+            let result = fut.await;
+
+            let status = result.as_ref().ok().map(Response::status);
+            metrics.record(status, start.elapsed());
+
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics() {
+        let metrics = Metrics::default();
+        assert_eq!(metrics.snapshot().average_latency(), None);
+
+        metrics.record(Some(StatusCode::OK), Duration::from_millis(10));
+        metrics.record(
+            Some(StatusCode::TOO_MANY_REQUESTS),
+            Duration::from_millis(20),
+        );
+        metrics.record(Some(StatusCode::NOT_FOUND), Duration::from_millis(30));
+        metrics.record(None, Duration::from_millis(40));
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.requests, 4);
+        assert_eq!(snapshot.errors, 3);
+        assert_eq!(snapshot.rate_limited, 1);
+        assert_eq!(snapshot.latency, Duration::from_millis(100));
+        assert_eq!(snapshot.average_latency(), Some(Duration::from_millis(25)));
+    }
+}
@@ -0,0 +1,451 @@
+//! Policy to retry failed requests, with exponential backoff and jitter,
+//! along with the middleware layer which applies it.
+//!
+use crate::api::rate_limit::RequestLimiter;
+use crate::api::span::record_retry;
+use crate::api::transport::{ResponseFuture, StreamResponse};
+use crate::log::debug;
+use crate::types::Result;
+use crate::RustWistiaError;
+
+use std::collections::hash_map::RandomState;
+use std::future::poll_fn;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime};
+
+use hyper::body::HttpBody;
+use hyper::header::{HeaderMap, RETRY_AFTER};
+use hyper::{Body, Request, Response, StatusCode};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Policy to retry requests which fail due to a rate limit (`429`), a
+/// server error (`500`, `502`, `503` or `504`), a timeout or a connection
+/// error.
+///
+/// By default, a request is attempted up to **3** times, and only
+/// *idempotent* requests (such as `GET`, `PUT` and `DELETE`) are retried;
+/// requests such as `POST` are only retried on an error when connecting,
+/// since the request was then never sent. Uploads can be opted in to
+/// retries, when the source can be re-read.
+///
+/// Between attempts, the client waits for an exponentially increasing
+/// backoff with *jitter*, or for the time given by a `Retry-After` header
+/// in the response.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use rust_wistia::{ClientBuilder, RetryPolicy};
+///
+/// let builder = ClientBuilder::new().retry_policy(
+///     RetryPolicy::default()
+///         .max_attempts(5)
+///         .initial_backoff(Duration::from_millis(250)),
+/// );
+///
+/// // Disable retries
+/// let builder = ClientBuilder::new().retry_policy(RetryPolicy::none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    max_retry_after: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            max_retry_after: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy which never retries a request.
+    pub fn none() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Sets the maximum number of attempts for a request, including the
+    /// first one (default: 3).
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the backoff before the first retry, which doubles for each
+    /// retry after that (default: 500 milliseconds).
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Sets the maximum backoff between attempts (default: 30 seconds).
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Sets whether to add random *jitter* to the backoff, which waits for
+    /// between half and the full backoff (default: `true`).
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the maximum time to wait for, as given by a `Retry-After`
+    /// header; if the server asks to wait any longer, the request is not
+    /// retried (default: 60 seconds).
+    pub fn max_retry_after(mut self, max_retry_after: Duration) -> Self {
+        self.max_retry_after = max_retry_after;
+        self
+    }
+
+    /// Checks if another attempt is allowed, after `attempt` (which starts
+    /// from 1) failed.
+    pub fn can_retry(&self, attempt: u32) -> bool {
+        attempt < self.max_attempts
+    }
+
+    /// Checks if a response with the status code should be retried.
+    pub fn is_retryable_status(&self, status: StatusCode) -> bool {
+        is_retryable_status(status.as_u16())
+    }
+
+    /// Returns the backoff before the next attempt, after `attempt` (which
+    /// starts from 1) failed.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+
+        if self.jitter {
+            let half = backoff / 2;
+            half + half.mul_f64(random_fraction())
+        } else {
+            backoff
+        }
+    }
+
+    /// Returns the delay before the next attempt, honoring a `Retry-After`
+    /// header in the response, or `None` if the server asks to wait for
+    /// longer than the maximum allowed.
+    pub(crate) fn delay(&self, attempt: u32, headers: Option<&HeaderMap>) -> Option<Duration> {
+        match headers.and_then(retry_after) {
+            Some(delay) if delay > self.max_retry_after => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+}
+
+/// Which requests may be retried, which depends on whether the request
+/// (and its body) can be sent again.
+///
+/// This is passed to the [`RetryService`] as a request extension; without
+/// it, a request is retried as per `Idempotent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Retry {
+    /// The request cannot be sent again, such as for a stream upload.
+    Never,
+    /// Retry idempotent requests, and any request on a connection error.
+    Idempotent,
+    /// Retry any request, such as an upload which was opted in.
+    #[cfg(feature = "upload-url")]
+    Always,
+}
+
+/// Builds a request again, so that it can be retried.
+///
+/// This is passed to the [`RetryService`] as a request extension; without
+/// it, only a request with an empty body can be retried.
+#[derive(Clone)]
+pub(crate) struct Replay(Arc<dyn Fn() -> Result<Request<Body>> + Send + Sync>);
+
+impl Replay {
+    pub(crate) fn new<F>(make_req: F) -> Self
+    where
+        F: Fn() -> Result<Request<Body>> + Send + Sync + 'static,
+    {
+        Self(Arc::new(make_req))
+    }
+
+    /// Build a new instance of the request.
+    pub(crate) fn make(&self) -> Result<Request<Body>> {
+        (self.0)()
+    }
+
+    /// Returns a `Replay` which runs `f` on each request, after it is built.
+    pub(crate) fn map<F>(self, f: F) -> Self
+    where
+        F: Fn(&mut Request<Body>) + Send + Sync + 'static,
+    {
+        Self::new(move || {
+            let mut req = self.make()?;
+            f(&mut req);
+            Ok(req)
+        })
+    }
+
+    /// Copy the extensions of `req` which the inner services rely on to
+    /// each request which is built again.
+    fn with_extensions(self, req: &Request<Body>) -> Self {
+        let limiter = req.extensions().get::<RequestLimiter>().cloned();
+        let stream = req.extensions().get::<StreamResponse>().copied();
+
+        self.map(move |req| {
+            if let Some(limiter) = &limiter {
+                req.extensions_mut().insert(limiter.clone());
+            }
+            if let Some(stream) = stream {
+                req.extensions_mut().insert(stream);
+            }
+        })
+    }
+
+    /// Returns a `Replay` which copies the request, if it has an empty body.
+    fn from_empty(req: &Request<Body>) -> Option<Self> {
+        if !req.body().is_end_stream() {
+            return None;
+        }
+
+        let method = req.method().clone();
+        let uri = req.uri().clone();
+        let version = req.version();
+        let headers = req.headers().clone();
+
+        Some(Self::new(move || {
+            let mut req = Request::new(Body::empty());
+            *req.method_mut() = method.clone();
+            *req.uri_mut() = uri.clone();
+            *req.version_mut() = version;
+            *req.headers_mut() = headers.clone();
+            Ok(req)
+        }))
+    }
+}
+
+/// A [`Layer`] which retries failed requests as per a [`RetryPolicy`].
+///
+/// This is already applied to the clients by the [`ClientBuilder`], as
+/// configured by [`ClientBuilder::retry_policy`].
+///
+/// [`ClientBuilder`]: crate::ClientBuilder
+/// [`ClientBuilder::retry_policy`]: crate::ClientBuilder::retry_policy
+#[derive(Debug, Clone, Default)]
+pub struct RetryLayer {
+    policy: RetryPolicy,
+}
+
+impl RetryLayer {
+    /// Create a new `RetryLayer` with a retry policy.
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+impl<S> Layer<S> for RetryLayer {
+    type Service = RetryService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RetryService {
+            inner,
+            policy: self.policy.clone(),
+        }
+    }
+}
+
+/// A [`Service`] which retries failed requests as per a [`RetryPolicy`].
+///
+/// A request with a body is only retried if it was sent by one of the
+/// clients in this crate, which know how to build the request again.
+#[derive(Debug, Clone)]
+pub struct RetryService<S> {
+    inner: S,
+    policy: RetryPolicy,
+}
+
+impl<S> Service<Request<Body>> for RetryService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>, Error = RustWistiaError>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+{
+    type Response = Response<Body>;
+    type Error = RustWistiaError;
+    type Future = ResponseFuture;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        // Use the service which was driven to readiness, and leave a clone
+        // in its place.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let policy = self.policy.clone();
+
+        Box::pin(async move {
+            let retry = req
+                .extensions()
+                .get::<Retry>()
+                .copied()
+                .unwrap_or(Retry::Idempotent);
+            let replay = match retry {
+                Retry::Never => None,
+                _ => req
+                    .extensions()
+                    .get::<Replay>()
+                    .cloned()
+                    .or_else(|| Replay::from_empty(&req))
+                    .map(|replay| replay.with_extensions(&req)),
+            };
+            let retry_any = match retry {
+                Retry::Never => false,
+                Retry::Idempotent => req.method().is_idempotent(),
+                #[cfg(feature = "upload-url")]
+                Retry::Always => true,
+            };
+
+            let mut req = Some(req);
+            let mut attempt = 1;
+
+            loop {
+                let req = match (req.take(), &replay) {
+                    (Some(req), _) => req,
+                    (None, Some(replay)) => replay.make()?,
+                    (None, None) => return Err(RustWistiaError::Unknown),
+                };
+                let can_retry = replay.is_some() && policy.can_retry(attempt);
+
+                let result = inner.call(req).await;
+
+                let delay = match &result {
+                    Ok(resp)
+                        if can_retry && retry_any && policy.is_retryable_status(resp.status()) =>
+                    {
+                        policy.delay(attempt, Some(resp.headers()))
+                    }
+                    // A request which failed to connect was never sent, so
+                    // it can be retried even if it is not idempotent.
+                    Err(e) if can_retry && e.is_retryable() && (retry_any || is_connect(e)) => {
+                        policy.delay(attempt, None)
+                    }
+                    _ => None,
+                };
+
+                match delay {
+                    Some(delay) => {
+                        debug!("Attempt {attempt} failed, retrying in {delay:.2?}");
+                        tokio::time::sleep(delay).await;
+                        poll_fn(|cx| inner.poll_ready(cx)).await?;
+                        attempt += 1;
+                        record_retry(attempt - 1);
+                    }
+                    None => return result,
+                }
+            }
+        })
+    }
+}
+
+/// Checks if a response with the `status` code might succeed if the request
+/// is sent again, such as after it is rate limited or a server error.
+pub(crate) fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 429 | 500 | 502 | 503 | 504)
+}
+
+/// Checks if the error is a failure to connect to the server.
+fn is_connect(error: &RustWistiaError) -> bool {
+    matches!(error, RustWistiaError::Hyper(e) if e.is_connect())
+}
+
+/// Parse the `Retry-After` header, which is either a number of seconds or
+/// an HTTP date.
+///
+/// See: <https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Retry-After>
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    match value.parse::<u64>() {
+        Ok(secs) => Some(Duration::from_secs(secs)),
+        Err(_) => {
+            let date = httpdate::parse_http_date(value).ok()?;
+            Some(
+                date.duration_since(SystemTime::now())
+                    .unwrap_or(Duration::ZERO),
+            )
+        }
+    }
+}
+
+/// Returns a random number in the range `[0, 1)`.
+///
+/// Each `RandomState` is seeded with different random keys, which is
+/// plenty for jitter without another dependency.
+fn random_fraction() -> f64 {
+    let n = RandomState::new().build_hasher().finish();
+    (n >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use hyper::header::HeaderValue;
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::default()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(350))
+            .jitter(false);
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+        assert_eq!(policy.backoff(40), Duration::from_millis(350));
+
+        let policy = policy.jitter(true);
+        for attempt in 1..5 {
+            let backoff = policy.backoff(attempt);
+            let max = policy.clone().jitter(false).backoff(attempt);
+            assert!(backoff >= max / 2 && backoff <= max, "{backoff:?}");
+        }
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("3"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(3)));
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+
+        let policy = RetryPolicy::default().max_retry_after(Duration::from_secs(10));
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(policy.delay(1, Some(&headers)), None);
+    }
+}
@@ -0,0 +1,177 @@
+//! A span for each call to the Wistia API, which is emitted via `tracing`
+//! with the `tracing` feature; otherwise, this only measures the latency.
+//!
+//! The span records the (redacted) endpoint, media id, status, latency,
+//! number of retries, and bytes transferred of each call.
+//!
+#[cfg(feature = "tracing")]
+use crate::utils::redact_url;
+use crate::RustWistiaError;
+
+use std::future::Future;
+#[cfg(any(feature = "logging", feature = "tracing"))]
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "tracing")]
+use hyper::body::HttpBody;
+#[cfg(feature = "tracing")]
+use hyper::header::CONTENT_LENGTH;
+use hyper::{Body, Request, Response};
+
+/// A span for a call to the Wistia API, which records the outcome of the
+/// call once it completes.
+pub(crate) struct CallSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(any(feature = "logging", feature = "tracing"))]
+    start: Instant,
+}
+
+impl CallSpan {
+    /// Start a span for a request to the `api` (i.e. `data` or `upload`).
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn new(api: &'static str, req: &Request<Body>) -> Self {
+        #[cfg(feature = "tracing")]
+        let span = {
+            use tracing::field::Empty;
+
+            let span = tracing::info_span!(
+                "wistia_api",
+                api,
+                method = %req.method(),
+                endpoint = %redact_url(&req.uri().to_string()),
+                media_id = Empty,
+                status = Empty,
+                latency_ms = Empty,
+                retries = Empty,
+                request_bytes = Empty,
+                response_bytes = Empty,
+                error = Empty,
+            );
+
+            if let Some(media_id) = media_id(req.uri().path()) {
+                span.record("media_id", media_id);
+            }
+            if let Some(len) = req.body().size_hint().exact() {
+                span.record("request_bytes", len);
+            }
+
+            span
+        };
+
+        Self {
+            #[cfg(feature = "tracing")]
+            span,
+            #[cfg(any(feature = "logging", feature = "tracing"))]
+            start: Instant::now(),
+        }
+    }
+
+    /// Run a future (which sends the request) within the span, so that any
+    /// retries are recorded on it.
+    pub(crate) async fn instrument<F: Future>(&self, fut: F) -> F::Output {
+        #[cfg(feature = "tracing")]
+        let fut = tracing::Instrument::instrument(fut, self.span.clone());
+
+        fut.await
+    }
+
+    /// The time elapsed since the call was started.
+    #[cfg(any(feature = "logging", feature = "tracing"))]
+    pub(crate) fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Record the status, latency and (if known) size of a response.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn record_response(&self, resp: &Response<Body>) {
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("status", resp.status().as_u16());
+            self.span
+                .record("latency_ms", self.elapsed().as_millis() as u64);
+
+            let len = resp
+                .headers()
+                .get(CONTENT_LENGTH)
+                .and_then(|value| value.to_str().ok()?.parse::<u64>().ok())
+                .or_else(|| resp.body().size_hint().exact());
+            if let Some(len) = len {
+                self.record_response_bytes(len);
+            }
+        }
+    }
+
+    /// Record the size of a response, once its body has been read.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn record_response_bytes(&self, len: u64) {
+        #[cfg(feature = "tracing")]
+        self.span.record("response_bytes", len);
+    }
+
+    /// Record the hashed id of a media, such as one which is uploaded.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn record_media_id(&self, media_id: &str) {
+        #[cfg(feature = "tracing")]
+        self.span.record("media_id", media_id);
+    }
+
+    /// Record an error on the span, returning it as-is.
+    pub(crate) fn record_error(&self, err: RustWistiaError) -> RustWistiaError {
+        #[cfg(feature = "tracing")]
+        {
+            if self.span.is_disabled() {
+                return err;
+            }
+            if let RustWistiaError::Request { status_code, .. } = &err {
+                self.span.record("status", status_code);
+            }
+            self.span
+                .record("latency_ms", self.elapsed().as_millis() as u64);
+            self.span.record("error", tracing::field::display(&err));
+        }
+
+        err
+    }
+}
+
+/// Record the number of retries on the current span, if it is the span for
+/// a call to the Wistia API; this is left empty if a call is not retried.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn record_retry(retries: u32) {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("retries", retries);
+}
+
+/// Returns the hashed id of the media in a path, such as
+/// `/v1/medias/abc1234567.json`.
+#[cfg(feature = "tracing")]
+fn media_id(path: &str) -> Option<&str> {
+    let mut segments = path.split('/');
+    segments.find(|segment| *segment == "medias")?;
+
+    segments
+        .next()
+        .map(|id| id.trim_end_matches(".json"))
+        .filter(|id| !id.is_empty())
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_media_id() {
+        assert_eq!(media_id("/v1/medias/abc1234567.json"), Some("abc1234567"));
+        assert_eq!(
+            media_id("/v1/medias/abc1234567/stats.json"),
+            Some("abc1234567")
+        );
+        assert_eq!(
+            media_id("/v1/stats/medias/abc1234567.json"),
+            Some("abc1234567")
+        );
+        assert_eq!(media_id("/v1/medias.json"), None);
+        assert_eq!(media_id("/v1/projects.json"), None);
+    }
+}
@@ -0,0 +1,483 @@
+//! The HTTP transport used by the Wistia API clients, which is a
+//! [`tower::Service`] so that it can be wrapped in middleware [`Layer`]s.
+//!
+//! [`tower::Service`]: tower_service::Service
+//! [`Layer`]: tower_layer::Layer
+//!
+use crate::https::HttpsClient;
+use crate::types::Result;
+use crate::utils::{is_insecure, redact_url};
+use crate::RustWistiaError;
+
+use std::fmt;
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+use hyper::body::{Bytes, HttpBody};
+use hyper::header::{HeaderValue, CONTENT_LENGTH};
+use hyper::{Body, Request, Response};
+use tokio::time::{Instant, Sleep};
+use tower_service::Service;
+
+/// A type-erased error, as raised by a middleware layer.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The future returned by a [`Transport`].
+pub type ResponseFuture = Pin<Box<dyn Future<Output = Result<Response<Body>>> + Send>>;
+
+/// A transport which sends requests for the Wistia API clients, which are
+/// generic over it.
+///
+/// This is implemented for any cloneable [`Service`] from a `Request<Body>`
+/// to a `Response<Body>`, which returns a [`ResponseFuture`]. By default,
+/// the clients use a [`BoxTransport`] which sends requests with hyper, and
+/// a [`MockTransport`] can be used in tests instead.
+///
+/// [`MockTransport`]: crate::mock::MockTransport
+pub trait Transport:
+    Service<
+        Request<Body>,
+        Response = Response<Body>,
+        Error = RustWistiaError,
+        Future = ResponseFuture,
+    > + Clone
+    + Send
+    + Sync
+    + 'static
+{
+}
+
+impl<S> Transport for S where
+    S: Service<
+            Request<Body>,
+            Response = Response<Body>,
+            Error = RustWistiaError,
+            Future = ResponseFuture,
+        > + Clone
+        + Send
+        + Sync
+        + 'static
+{
+}
+
+/// The transport which sends a single HTTP request with the hyper client,
+/// and reads the full response body.
+///
+/// The read timeout applies while waiting on the server: for the response
+/// headers once the request body is sent, and for each chunk of the
+/// response body. While a request body (such as an upload) is being sent,
+/// it only fails if no chunk is sent for that long. The total timeout
+/// applies to the whole request.
+///
+/// The response body is read in full (and buffered in memory) before the
+/// response is returned, so that the timeouts cover the whole body. A
+/// download, such as via [`DataClient::download_asset_to`], is streamed
+/// instead, with the same timeouts applied to each chunk as it is read.
+///
+/// [`DataClient::download_asset_to`]: crate::DataClient::download_asset_to
+///
+/// A request to a plain `http://` URL fails with a
+/// [`RustWistiaError::InsecureUrl`], unless the host is `localhost`.
+#[derive(Debug, Clone)]
+pub struct HttpsTransport {
+    client: HttpsClient,
+    read_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+}
+
+impl HttpsTransport {
+    /// Create a new transport from an HTTPS client, with no timeouts.
+    pub fn new(client: HttpsClient) -> Self {
+        Self {
+            client,
+            read_timeout: None,
+            timeout: None,
+        }
+    }
+
+    /// Sets the timeout for each read, that is while waiting on the
+    /// response headers or the next chunk of the response body, or on the
+    /// next chunk of the request body to be sent.
+    pub fn read_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.read_timeout = timeout;
+        self
+    }
+
+    /// Sets the total timeout for a request, from when it is sent until the
+    /// response body is fully read.
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl Service<Request<Body>> for HttpsTransport {
+    type Response = Response<Body>;
+    type Error = RustWistiaError;
+    type Future = ResponseFuture;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        // Never send the access token in cleartext, such as to an absolute
+        // `http://` URL passed to `DataClient::get()`.
+        if is_insecure(req.uri()) {
+            let url = redact_url(&req.uri().to_string());
+            return Box::pin(async move { Err(RustWistiaError::InsecureUrl { url }) });
+        }
+
+        let client = self.client.clone();
+        let read_timeout = self.read_timeout;
+        let timeout = self.timeout;
+        let stream = req.extensions().get::<StreamResponse>().is_some();
+
+        Box::pin(async move {
+            let deadline = timeout.map(|timeout| (Instant::now() + timeout, timeout));
+            let progress = Progress::new();
+            let req = progress.track(req);
+
+            let send_and_read = async {
+                let resp =
+                    with_idle_timeout(read_timeout, &progress, client.request(req)).await??;
+                let (parts, mut body) = resp.into_parts();
+
+                if stream {
+                    let body = TimedBody::new(body, read_timeout, deadline);
+                    return Ok(Response::from_parts(parts, Body::wrap_stream(body)));
+                }
+
+                let mut data = Vec::new();
+                while let Some(chunk) = with_timeout(read_timeout, body.data()).await? {
+                    data.extend_from_slice(&chunk?);
+                }
+
+                Ok(Response::from_parts(parts, Body::from(data)))
+            };
+
+            with_timeout(timeout, send_and_read).await?
+        })
+    }
+}
+
+/// Marks a request whose response body is streamed by the
+/// [`HttpsTransport`], rather than read in full, such as a download.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StreamResponse;
+
+/// When a request last made progress, that is when it was sent or when the
+/// last chunk of its body was sent, so that the read timeout only applies
+/// while waiting on the server.
+#[derive(Clone)]
+struct Progress(Arc<Mutex<Instant>>);
+
+impl Progress {
+    fn new() -> Self {
+        Self(Arc::new(Mutex::new(Instant::now())))
+    }
+
+    fn last(&self) -> Instant {
+        *self.0.lock().unwrap()
+    }
+
+    fn touch(&self) {
+        *self.0.lock().unwrap() = Instant::now();
+    }
+
+    /// Track the progress of sending the body of `req`, if it has one.
+    fn track(&self, req: Request<Body>) -> Request<Body> {
+        if req.body().is_end_stream() {
+            return req;
+        }
+
+        let (mut parts, body) = req.into_parts();
+
+        // A wrapped body has no size, so keep its length if it is known.
+        if let Some(len) = HttpBody::size_hint(&body).exact() {
+            parts
+                .headers
+                .entry(CONTENT_LENGTH)
+                .or_insert_with(|| HeaderValue::from(len));
+        }
+
+        let body = TrackedBody {
+            body,
+            progress: self.clone(),
+        };
+
+        Request::from_parts(parts, Body::wrap_stream(body))
+    }
+}
+
+/// A request body, which records its [`Progress`] as each chunk is sent.
+struct TrackedBody {
+    body: Body,
+    progress: Progress,
+}
+
+impl Stream for TrackedBody {
+    type Item = hyper::Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let poll = Pin::new(&mut self.body).poll_data(cx);
+        if let Poll::Ready(Some(Ok(_))) = &poll {
+            self.progress.touch();
+        }
+        poll
+    }
+}
+
+/// A streamed response body, which fails with a [`RustWistiaError::Timeout`]
+/// if a chunk is not read within the read timeout, or if the total timeout
+/// of the request has passed.
+struct TimedBody {
+    body: Body,
+    read_timeout: Option<(Pin<Box<Sleep>>, Duration)>,
+    deadline: Option<(Pin<Box<Sleep>>, Duration)>,
+    done: bool,
+}
+
+impl TimedBody {
+    fn new(
+        body: Body,
+        read_timeout: Option<Duration>,
+        deadline: Option<(Instant, Duration)>,
+    ) -> Self {
+        Self {
+            body,
+            read_timeout: read_timeout
+                .map(|timeout| (Box::pin(tokio::time::sleep(timeout)), timeout)),
+            deadline: deadline
+                .map(|(deadline, timeout)| (Box::pin(tokio::time::sleep_until(deadline)), timeout)),
+            done: false,
+        }
+    }
+}
+
+impl Stream for TimedBody {
+    type Item = Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+
+        if let Some((deadline, timeout)) = &mut self.deadline {
+            if deadline.as_mut().poll(cx).is_ready() {
+                let timeout = *timeout;
+                self.done = true;
+                return Poll::Ready(Some(Err(RustWistiaError::Timeout(timeout))));
+            }
+        }
+
+        match Pin::new(&mut self.body).poll_data(cx) {
+            Poll::Ready(chunk) => {
+                if let Some((sleep, timeout)) = &mut self.read_timeout {
+                    let next = Instant::now() + *timeout;
+                    sleep.as_mut().reset(next);
+                }
+                Poll::Ready(chunk.map(|chunk| chunk.map_err(RustWistiaError::from)))
+            }
+            Poll::Pending => {
+                if let Some((sleep, timeout)) = &mut self.read_timeout {
+                    if sleep.as_mut().poll(cx).is_ready() {
+                        let timeout = *timeout;
+                        self.done = true;
+                        return Poll::Ready(Some(Err(RustWistiaError::Timeout(timeout))));
+                    }
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Convert an error from reading a streamed response body, which may be a
+/// timeout raised by the [`HttpsTransport`].
+pub(crate) fn body_error(err: hyper::Error) -> RustWistiaError {
+    let is_ours =
+        std::error::Error::source(&err).is_some_and(|cause| cause.is::<RustWistiaError>());
+    if !is_ours {
+        return RustWistiaError::Hyper(err);
+    }
+
+    match err
+        .into_cause()
+        .map(|cause| cause.downcast::<RustWistiaError>())
+    {
+        Some(Ok(err)) => *err,
+        _ => RustWistiaError::Unknown,
+    }
+}
+
+/// A type-erased transport, which is what the clients use to send requests.
+///
+/// This wraps any cloneable [`Service`] from a `Request<Body>` to a
+/// `Response<Body>`, such as the [`HttpsTransport`] wrapped in any number
+/// of middleware layers. Errors which are not a [`RustWistiaError`] are
+/// returned as a [`RustWistiaError::Transport`].
+pub struct BoxTransport {
+    // The `Mutex` is only locked to clone the service, so that the
+    // transport (and the clients) are `Sync` even if the service is not.
+    inner: Mutex<Box<dyn CloneTransport>>,
+}
+
+impl BoxTransport {
+    /// Wrap a service as a `BoxTransport`.
+    pub fn new<S>(service: S) -> Self
+    where
+        S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+        S::Error: Into<BoxError>,
+        S::Future: Send + 'static,
+    {
+        Self {
+            inner: Mutex::new(Box::new(service)),
+        }
+    }
+}
+
+impl Clone for BoxTransport {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Mutex::new(self.inner.lock().unwrap().clone_box()),
+        }
+    }
+}
+
+impl fmt::Debug for BoxTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxTransport").finish_non_exhaustive()
+    }
+}
+
+impl Service<Request<Body>> for BoxTransport {
+    type Response = Response<Body>;
+    type Error = RustWistiaError;
+    type Future = ResponseFuture;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.get_mut().unwrap().poll_ready_box(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        self.inner.get_mut().unwrap().call_box(req)
+    }
+}
+
+/// An object-safe, cloneable [`Service`].
+trait CloneTransport: Send {
+    fn clone_box(&self) -> Box<dyn CloneTransport>;
+
+    fn poll_ready_box(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>>;
+
+    fn call_box(&mut self, req: Request<Body>) -> ResponseFuture;
+}
+
+impl<S> CloneTransport for S
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+{
+    fn clone_box(&self) -> Box<dyn CloneTransport> {
+        Box::new(self.clone())
+    }
+
+    fn poll_ready_box(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.poll_ready(cx).map_err(into_error)
+    }
+
+    fn call_box(&mut self, req: Request<Body>) -> ResponseFuture {
+        let fut = self.call(req);
+        Box::pin(async move { fut.await.map_err(into_error) })
+    }
+}
+
+/// Convert an error raised by a service into a `RustWistiaError`.
+fn into_error<E: Into<BoxError>>(err: E) -> RustWistiaError {
+    match err.into().downcast::<RustWistiaError>() {
+        Ok(err) => *err,
+        Err(err) => RustWistiaError::Transport(err),
+    }
+}
+
+/// Await a future, failing if the request makes no [`Progress`] for the
+/// `timeout`.
+async fn with_idle_timeout<F: Future>(
+    timeout: Option<Duration>,
+    progress: &Progress,
+    fut: F,
+) -> Result<F::Output> {
+    let Some(timeout) = timeout else {
+        return Ok(fut.await);
+    };
+    let mut fut = pin!(fut);
+
+    loop {
+        match tokio::time::timeout_at(progress.last() + timeout, &mut fut).await {
+            Ok(output) => return Ok(output),
+            // A chunk of the request body was sent in the meantime.
+            Err(_) if progress.last().elapsed() < timeout => continue,
+            Err(_) => return Err(RustWistiaError::Timeout(timeout)),
+        }
+    }
+}
+
+/// Await a future, with an optional timeout.
+async fn with_timeout<F: Future>(timeout: Option<Duration>, fut: F) -> Result<F::Output> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, fut)
+            .await
+            .map_err(|_| RustWistiaError::Timeout(timeout)),
+        None => Ok(fut.await),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_streamed_body_read_timeout() {
+        let (mut sender, body) = Body::channel();
+        let timeout = Duration::from_secs(1);
+        let mut body = Body::wrap_stream(TimedBody::new(body, Some(timeout), None));
+
+        sender.send_data("chunk".into()).await.unwrap();
+        assert_eq!(body.data().await.unwrap().unwrap(), "chunk");
+
+        // The sender stalls, so the next read times out
+        let err = body_error(body.data().await.unwrap().unwrap_err());
+        assert!(matches!(err, RustWistiaError::Timeout(t) if t == timeout));
+        drop(sender);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_streamed_body_total_timeout() {
+        let (mut sender, body) = Body::channel();
+        let timeout = Duration::from_secs(1);
+        let deadline = Some((Instant::now() + timeout, timeout));
+        let mut body = Body::wrap_stream(TimedBody::new(body, None, deadline));
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_millis(300)).await;
+                if sender.send_data("chunk".into()).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let err = loop {
+            if let Err(err) = body.data().await.unwrap() {
+                break body_error(err);
+            }
+        };
+        assert!(matches!(err, RustWistiaError::Timeout(t) if t == timeout));
+    }
+}
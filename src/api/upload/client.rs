@@ -1,20 +1,43 @@
-use crate::constants::{ENV_VAR_NAME, UPLOAD_API};
-use crate::https::{get_https_client, tls};
+use crate::api::builder::{ClientBuilder, RequestOptions};
+use crate::api::retry::{Replay, Retry};
+use crate::api::span::CallSpan;
+use crate::api::transport::{BoxTransport, Transport};
+use crate::auth::{AccessToken, TokenProvider};
+use crate::constants::UPLOAD_API;
+use crate::https::get_https_client;
 use crate::log::debug;
 use crate::models::*;
-use crate::status::raise_for_status;
+use crate::status::check_status;
 use crate::types::Result;
-use crate::utils::into_struct_from_slice;
-use crate::RustWistiaError;
+use crate::utils::{into_struct_from_slice, validate_base_url, REDACTED};
 
-use std::env::var;
-use std::time::Instant;
+use std::fmt;
+use std::sync::Arc;
 
-use hyper::body::HttpBody;
-use hyper::client::HttpConnector;
-use hyper::{Body, Client, Request};
+#[cfg(feature = "upload-url")]
+use hyper::header::AUTHORIZATION;
+#[cfg(feature = "upload-url")]
+use hyper::http::request::Builder;
+use hyper::{Body, Request};
 use serde_urlencoded::to_string;
 
+/// How the access token is sent with a request to the Wistia Upload API.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UploadAuth {
+    /// As a bearer token in the `Authorization` header (the default).
+    #[default]
+    Bearer,
+    /// As an `access_token` field in the (multipart or url-encoded) form
+    /// body of the request.
+    Form,
+    /// As an `access_token` *query parameter*, as in earlier versions of
+    /// this library.
+    ///
+    /// This is only kept for compatibility, as the URL (and so the token)
+    /// may end up in the logs of a proxy or a server.
+    Query,
+}
+
 /// Client used to make requests  to the Wistia **[Upload API]**.
 ///
 /// # Note
@@ -23,102 +46,277 @@ use serde_urlencoded::to_string;
 ///
 /// Also check out the [`rust-wistia`] docs for usage and examples.
 ///
+/// The client is generic over the [`Transport`] which sends requests, which
+/// is hyper by default.
+///
 /// [`rust-wistia`]: https://docs.rs/rust-wistia
 /// [Upload API]: https://wistia.com/support/developers/upload-api
 ///
 #[derive(Clone)]
-pub struct UploadClient<B = Body> {
-    /// Represents the [API access token] used to authenticate requests to the
+pub struct UploadClient<T = BoxTransport> {
+    /// Provides the [API access token] used to authenticate requests to the
     /// [Wistia API].
     ///
     /// [API access token]: https://wistia.com/support/developers/data-api#getting-started
     /// [Wistia API]: https://wistia.com/support/developers/upload-api
-    pub access_token: String,
-    /// The HTTPS client to use for sending requests.
-    client: Client<tls::HttpsConnector<HttpConnector>, B>,
+    token: Arc<dyn TokenProvider>,
+    /// The base URL of the Wistia Upload API.
+    upload_url: String,
+    /// How the access token is sent with each request.
+    auth: UploadAuth,
+    /// The options applied to each request, along with the transport which
+    /// sends it.
+    options: Arc<RequestOptions<T>>,
 }
 
-impl<B: HttpBody + Send + 'static> From<String> for UploadClient<B>
-where
-    <B as HttpBody>::Data: Send,
-    <B as HttpBody>::Error: Into<Box<(dyn std::error::Error + Send + Sync + 'static)>>,
-{
+impl From<AccessToken> for UploadClient {
+    /// Create a new `UploadClient` from an access token
+    fn from(token: AccessToken) -> Self {
+        Self::from_parts(
+            Arc::new(token),
+            UPLOAD_API.to_owned(),
+            RequestOptions::new(get_https_client()),
+        )
+    }
+}
+
+impl From<String> for UploadClient {
     /// Create a new `UploadClient` from an access token
     fn from(token: String) -> Self {
-        Self {
-            access_token: token,
-            client: get_https_client(),
-        }
+        Self::from(AccessToken::from(token))
     }
 }
 
-impl<B: HttpBody + Send + 'static> From<&str> for UploadClient<B>
-where
-    <B as HttpBody>::Data: Send,
-    <B as HttpBody>::Error: Into<Box<(dyn std::error::Error + Send + Sync + 'static)>>,
-{
+impl From<&str> for UploadClient {
     /// Create a new `UploadClient` from an access token
     fn from(token: &str) -> Self {
-        Self {
-            access_token: token.to_string(),
-            client: get_https_client(),
-        }
+        Self::from(AccessToken::from(token))
     }
 }
 
-impl<B: HttpBody + Send + 'static> UploadClient<B>
-where
-    <B as HttpBody>::Data: Send,
-    <B as HttpBody>::Error: Into<Box<(dyn std::error::Error + Send + Sync + 'static)>>,
-{
+impl UploadClient {
     /// Initialize a new `UploadClient` object from an [API access token],
     /// assuming this is currently set in the environment.
     ///
+    /// The base URL of the Upload API can also be overridden, via the
+    /// `WISTIA_UPLOAD_URL` environment variable.
+    ///
     /// [API access token]: https://wistia.com/support/developers/data-api#getting-started
     pub fn from_env() -> Result<Self> {
-        let token = match var(ENV_VAR_NAME) {
-            Ok(val) => Ok(val),
-            Err(_) => Err(RustWistiaError::EnvVarNotFound {
-                name: ENV_VAR_NAME.to_owned(),
-            }),
-        }?;
-
-        Ok(Self::from(token))
+        ClientBuilder::new().build_upload()
     }
 
     /// Initialize a new `UploadClient` object from an [API access token].
     ///
     /// [API access token]: https://wistia.com/support/developers/data-api#getting-started
-    pub fn from_token(token: &str) -> Self {
-        Self::from(token)
+    pub fn from_token(token: impl Into<AccessToken>) -> Self {
+        Self::from(token.into())
+    }
+
+    /// Returns a [`ClientBuilder`] to configure a new `UploadClient`, such
+    /// as to set timeouts or a custom User-Agent.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+}
+
+impl<T> fmt::Debug for UploadClient<T> {
+    /// The access token is redacted from the output.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadClient")
+            .field("access_token", &format_args!("{REDACTED}"))
+            .field("upload_url", &self.upload_url)
+            .field("auth", &self.auth)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Transport> UploadClient<T> {
+    /// Create a new `UploadClient` from an access token, which sends
+    /// requests with a custom `transport`.
+    ///
+    /// The transport is used as-is, so requests are not retried unless it
+    /// is wrapped in a [`RetryLayer`](crate::RetryLayer).
+    pub fn with_transport(token: impl Into<AccessToken>, transport: T) -> Self {
+        Self::from_parts(
+            Arc::new(token.into()),
+            UPLOAD_API.to_owned(),
+            RequestOptions::with_transport(transport),
+        )
+    }
+
+    /// Create a new `UploadClient` from its parts, so that an existing
+    /// transport (and its connection pool) can be shared.
+    pub(crate) fn from_parts(
+        token: Arc<dyn TokenProvider>,
+        upload_url: String,
+        options: RequestOptions<T>,
+    ) -> Self {
+        Self {
+            token,
+            upload_url,
+            auth: UploadAuth::default(),
+            options: Arc::new(options),
+        }
+    }
+
+    /// Sets the [`TokenProvider`] which is consulted for the access token on
+    /// each upload, so that the token can be rotated.
+    pub fn with_token_provider(mut self, provider: impl TokenProvider) -> Self {
+        self.token = Arc::new(provider);
+        self
+    }
+
+    /// Returns a clone of the client which authenticates with another access
+    /// `token`, such as for an upload on behalf of another account. The
+    /// clone shares the same connection pool and other options, while any
+    /// [`RateLimit`](crate::RateLimit) applies to the new token on its own.
+    pub fn for_token(&self, token: impl Into<AccessToken>) -> Self {
+        let token = token.into();
+        Self {
+            options: Arc::new(self.options.for_token(&token)),
+            token: Arc::new(token),
+            upload_url: self.upload_url.clone(),
+            auth: self.auth,
+        }
+    }
+
+    /// Returns the current [API access token], from the client's
+    /// [`TokenProvider`].
+    ///
+    /// [API access token]: https://wistia.com/support/developers/data-api#getting-started
+    pub async fn access_token(&self) -> Result<AccessToken> {
+        self.token.token().await
+    }
+
+    /// Returns the transport which sends each request.
+    pub fn transport(&self) -> &T {
+        self.options.transport()
+    }
+
+    /// Sets the base URL of the Wistia Upload API (default: `https://upload.wistia.com`),
+    /// for example to point the client at a recording proxy or a mock server.
+    ///
+    /// Plain HTTP is only allowed for a local host, such as `http://localhost:8080`.
+    pub fn with_upload_url(mut self, upload_url: &str) -> Result<Self> {
+        self.upload_url = validate_base_url(upload_url)?;
+        Ok(self)
+    }
+
+    /// Returns the base URL of the Wistia Upload API.
+    pub fn upload_url(&self) -> &str {
+        &self.upload_url
+    }
+
+    /// Sets how the access token is sent with each request (default: as a
+    /// bearer token in the `Authorization` header).
+    pub fn with_auth(mut self, auth: UploadAuth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Returns how the access token is sent with each request.
+    pub fn auth(&self) -> UploadAuth {
+        self.auth
     }
 
     /// Build the URL with the url-encoded *query parameters* included
-    pub fn build_url(params: UploadRequest) -> Result<String> {
+    pub fn build_url(&self, params: UploadRequest) -> Result<String> {
         let query = to_string(params)?;
 
+        if query.is_empty() {
+            return Ok(self.upload_url.clone());
+        }
+
         // Build the URL with the query parameters included
-        let mut url = String::with_capacity(UPLOAD_API.len() + 1 + query.len());
-        url.push_str(UPLOAD_API);
+        let mut url = String::with_capacity(self.upload_url.len() + 1 + query.len());
+        url.push_str(&self.upload_url);
         url.push('?');
         url.push_str(query.as_str());
 
         Ok(url)
     }
 
+    /// Returns the access token to include in the *query parameters*, which
+    /// is empty (and so omitted) unless it is sent via [`UploadAuth::Query`].
+    #[cfg(feature = "upload-url")]
+    pub(crate) fn query_token<'a>(&self, token: &'a AccessToken) -> &'a str {
+        match self.auth {
+            UploadAuth::Query => token.expose_secret(),
+            _ => "",
+        }
+    }
+
+    /// Returns the access token to include in the form body, if it is sent
+    /// via [`UploadAuth::Form`].
+    #[cfg(feature = "upload-url")]
+    pub(crate) fn form_token<'a>(&self, token: &'a AccessToken) -> Option<&'a str> {
+        match self.auth {
+            UploadAuth::Form => Some(token.expose_secret()),
+            _ => None,
+        }
+    }
+
+    /// Returns a builder for a `POST` request to `url`, with the access
+    /// token in the `Authorization` header if it is sent via
+    /// [`UploadAuth::Bearer`].
+    #[cfg(feature = "upload-url")]
+    pub(crate) fn post(&self, url: &str, token: &AccessToken) -> Result<Builder> {
+        let builder = Request::post(url);
+
+        Ok(match self.auth {
+            UploadAuth::Bearer => builder.header(AUTHORIZATION, token.bearer_header()?),
+            _ => builder,
+        })
+    }
+
     /// Send the request to the Wistia Upload API
+    ///
+    /// # Note
+    /// The request is *not* retried, as its body cannot be sent again.
     pub async fn make_request<'a>(
         &'a self,
         url: &'a str,
-        req: Request<B>,
+        req: Request<Body>,
+    ) -> Result<UploadResponse> {
+        // The token is only needed to redact it from an error response.
+        let token = self.access_token().await.ok();
+
+        self.send_request(url, req, token.as_ref(), Retry::Never, None)
+            .await
+    }
+
+    /// Send a request to the Wistia Upload API, which is built again by
+    /// `replay` for each retry, as permitted by `retry`.
+    ///
+    /// The access `token` which authenticates the request is redacted from
+    /// any error response.
+    pub(crate) async fn send_request(
+        &self,
+        url: &str,
+        req: Request<Body>,
+        token: Option<&AccessToken>,
+        retry: Retry,
+        replay: Option<Replay>,
     ) -> Result<UploadResponse> {
-        let start = Instant::now();
-        let mut resp = self.client.request(req).await?;
-        debug!("Call Upload API completed {:.2?}", start.elapsed());
+        let method = req.method().clone();
+        let span = CallSpan::new("upload", &req);
+        let mut resp = span
+            .instrument(self.options.send(req, retry, replay))
+            .await
+            .map_err(|e| span.record_error(e))?;
+        span.record_response(&resp);
+        debug!("Call Upload API completed {:.2?}", span.elapsed());
+
+        let secrets: Vec<&str> = token.iter().map(|t| t.expose_secret()).collect();
+        check_status(Some(&method), url, &mut resp, &secrets)
+            .await
+            .map_err(|e| span.record_error(e))?;
 
-        raise_for_status(url, &mut resp).await?;
+        let media: UploadResponse = into_struct_from_slice(resp).await?;
+        span.record_media_id(media.hashed_id.as_str());
 
-        into_struct_from_slice(resp).await
+        Ok(media)
     }
 }
 
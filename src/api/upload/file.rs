@@ -1,4 +1,7 @@
 use crate::api::client::UploadClient;
+use crate::api::retry::{Replay, Retry};
+use crate::api::transport::{BoxTransport, Transport};
+use crate::auth::AccessToken;
 use crate::models::*;
 use crate::types::Result;
 use crate::RustWistiaError;
@@ -7,10 +10,10 @@ use std::fmt::Debug;
 use std::io;
 use std::path::Path;
 
-use hyper::Request;
+use hyper::Body;
 use hyper_multipart::client::multipart::Form;
 use hyper_multipart_rfc7578 as hyper_multipart;
-use hyper_multipart_rfc7578::client::multipart::Body;
+use hyper_multipart_rfc7578::client::multipart;
 
 /// Client implementation to upload *files* and *videos* via the Wistia
 /// **[Upload API]**.
@@ -21,9 +24,10 @@ use hyper_multipart_rfc7578::client::multipart::Body;
 /// [Upload API]: https://wistia.com/support/developers/upload-api
 ///
 #[derive(Clone)]
-pub struct FileUploader<'a, P: AsRef<Path>, B = Body> {
-    client: UploadClient<B>,
+pub struct FileUploader<'a, P: AsRef<Path>, T = BoxTransport> {
+    client: UploadClient<T>,
     req: UploadFileRequest<'a, P>,
+    retry: bool,
 }
 
 impl<'a, P: AsRef<Path> + Debug> FileUploader<'a, P> {
@@ -33,12 +37,13 @@ impl<'a, P: AsRef<Path> + Debug> FileUploader<'a, P> {
     /// # Arguments
     ///
     /// * `file_path` - The path to the media file. The contents of this file
-    /// will be multipart-form encoded into the request body.
+    ///   will be multipart-form encoded into the request body.
     ///
     pub fn new(file_path: P) -> Result<Self> {
         Ok(Self {
             client: UploadClient::from_env()?,
             req: UploadFileRequest::new(file_path),
+            retry: false,
         })
     }
 
@@ -48,39 +53,41 @@ impl<'a, P: AsRef<Path> + Debug> FileUploader<'a, P> {
     /// # Arguments
     ///
     /// * `file_path` - The path to the media file. The contents of this file
-    /// will be multipart-form encoded into the request body.
+    ///   will be multipart-form encoded into the request body.
     /// * `access_token` - An API access token used to make requests to the
-    /// Wistia API.
+    ///   Wistia API.
     ///
-    pub fn with_token(file_path: P, access_token: &str) -> Self {
+    pub fn with_token(file_path: P, access_token: impl Into<AccessToken>) -> Self {
         Self {
             client: UploadClient::from_token(access_token),
             req: UploadFileRequest::new(file_path),
+            retry: false,
         }
     }
+}
 
+impl<'a, P: AsRef<Path> + Debug, T: Transport> FileUploader<'a, P, T> {
     /// Create a `FileUploader` with a file path and an HTTPS client.
     ///
     /// # Arguments
     ///
     /// * `file_path` - The path to the media file. The contents of this file
-    /// will be multipart-form encoded into the request body.
+    ///   will be multipart-form encoded into the request body.
     /// * `client` - The HTTPS client (UploadClient) to use for requests.
-    /// Note that the client must support multipart form requests, via a
-    /// `multipart::Body`.
     ///
-    pub fn with_client(file_path: P, client: UploadClient<Body>) -> Self {
+    pub fn with_client(file_path: P, client: UploadClient<T>) -> Self {
         Self {
             client,
             req: UploadFileRequest::new(file_path),
+            retry: false,
         }
     }
 
     /// The hashed id of the project to upload media into. If omitted, a new
     /// project will be created and uploaded to. The naming convention used
     /// for such projects is `Uploads_YYYY-MM-DD`.
-    pub fn project_id(mut self, project_id: &'a str) -> Self {
-        self.req.project_id = Some(project_id);
+    pub fn project_id(mut self, project_id: impl Into<ProjectHashedId>) -> Self {
+        self.req.project_id = Some(project_id.into());
         self
     }
 
@@ -105,6 +112,19 @@ impl<'a, P: AsRef<Path> + Debug> FileUploader<'a, P> {
         self
     }
 
+    /// Retry the upload if it fails due to a rate limit or a server error,
+    /// as per the [`RetryPolicy`] of the client. The file is read again for
+    /// each attempt.
+    ///
+    /// Uploads are not idempotent, so by default they are only retried on
+    /// an error when connecting.
+    ///
+    /// [`RetryPolicy`]: crate::RetryPolicy
+    pub fn retry_upload(mut self, retry: bool) -> Self {
+        self.retry = retry;
+        self
+    }
+
     /// Send the Upload File request (with the *multi-part form* data) to the
     /// Wistia [Upload API].
     ///
@@ -112,42 +132,69 @@ impl<'a, P: AsRef<Path> + Debug> FileUploader<'a, P> {
     ///
     // noinspection DuplicatedCode
     pub async fn send(&self) -> Result<UploadResponse> {
+        // The token is resolved once, rather than for each attempt
+        let token = self.client.access_token().await?;
+
         // Build the query parameters to pass to the Upload API
 
         let params = UploadRequest {
-            access_token: self.client.access_token.as_str(),
+            access_token: self.client.query_token(&token),
             url: None,
-            project_id: self.req.project_id,
+            project_id: self.req.project_id.as_ref().map(ProjectHashedId::as_str),
             name: self.req.name,
             description: None,
             contact_id: self.req.contact_id,
         };
 
-        let url = UploadClient::<Body>::build_url(params)?;
-
-        // Create a request instance and multipart form
-        let req_builder = Request::post(&url);
-        let mut form = Form::default();
-
-        // Add multi-part form fields
-
-        form.add_file("file", &self.req.file_path)
-            .map_err(|e: io::Error| match e.kind() {
-                io::ErrorKind::NotFound => RustWistiaError::FileNotFound(
-                    self.req.file_path.as_ref().to_string_lossy().to_string(),
-                ),
-                _ => RustWistiaError::Io(e),
-            })?;
-
-        if let Some(description) = self.req.description {
-            form.add_text("description", description);
-        }
-
-        // Update a request instance with the multipart Content-Type header
-        // and the payload data.
-        let form = form.set_body::<Body>(req_builder).unwrap();
+        let url = self.client.build_url(params)?;
+
+        // The request is built for each attempt, as the file contents are
+        // read (again) when it is sent.
+        let file_path = self.req.file_path.as_ref().to_path_buf();
+        let description = self.req.description.map(str::to_owned);
+        let req_url = url.clone();
+        let client = self.client.clone();
+        let req_token = token.clone();
+
+        let replay = Replay::new(move || {
+            // Create a request instance and multipart form
+            let req_builder = client.post(&req_url, &req_token)?;
+            let mut form = Form::default();
+
+            // Add multi-part form fields
+
+            if let Some(token) = client.form_token(&req_token) {
+                form.add_text("access_token", token);
+            }
+
+            form.add_file("file", &file_path)
+                .map_err(|e: io::Error| match e.kind() {
+                    io::ErrorKind::NotFound => {
+                        RustWistiaError::FileNotFound(file_path.to_string_lossy().to_string())
+                    }
+                    _ => RustWistiaError::Io(e),
+                })?;
+
+            if let Some(description) = &description {
+                form.add_text("description", description);
+            }
+
+            // Update a request instance with the multipart Content-Type header
+            // and the payload data, which is converted into a `hyper::Body` so
+            // that the client (and its connection pool) can be shared with the
+            // other APIs.
+            Ok(form.set_body_convert::<Body, multipart::Body>(req_builder)?)
+        });
+
+        let retry = if self.retry {
+            Retry::Always
+        } else {
+            Retry::Idempotent
+        };
 
         // Send the request
-        self.client.make_request(&url, form).await
+        self.client
+            .send_request(&url, replay.make()?, Some(&token), retry, Some(replay))
+            .await
     }
 }
@@ -1,6 +1,9 @@
 use crate::api::client::UploadClient;
+use crate::api::retry::{Replay, Retry};
+use crate::api::transport::{BoxTransport, Transport};
+use crate::auth::AccessToken;
 use crate::constants::DEFAULT_FILENAME;
-use crate::https::tls;
+use crate::https::HttpsClient;
 use crate::models::*;
 use crate::types::Result;
 use crate::utils::{stream_reader_from_url, stream_reader_from_url_and_arc_client};
@@ -8,11 +11,10 @@ use crate::utils::{stream_reader_from_url, stream_reader_from_url_and_arc_client
 use std::io::{Cursor, Read};
 use std::sync::Arc;
 
-use hyper::client::HttpConnector;
-use hyper::{body::Bytes, Client, Request};
+use hyper::{body::Bytes, Body, Request};
 use hyper_multipart::client::multipart::Form;
 use hyper_multipart_rfc7578 as hyper_multipart;
-use hyper_multipart_rfc7578::client::multipart::Body;
+use hyper_multipart_rfc7578::client::multipart;
 
 /// Client implementation to upload *streams* (file-like objects) and
 /// *videos* via the Wistia **[Upload API]**.
@@ -23,10 +25,12 @@ use hyper_multipart_rfc7578::client::multipart::Body;
 /// [Upload API]: https://wistia.com/support/developers/upload-api
 ///
 #[derive(Clone)]
-pub struct StreamUploader<'a, R: 'static + Read + Send + Sync, B = Body> {
-    client: UploadClient<B>,
+pub struct StreamUploader<'a, R: 'static + Read + Send + Sync, T = BoxTransport> {
+    client: UploadClient<T>,
     req: UploadStreamRequest<'a>,
     reader: Option<R>,
+    /// Used to clone the reader for each attempt, if the upload is retried.
+    clone_reader: Option<fn(&R) -> R>,
 }
 
 impl<'a> StreamUploader<'a, Cursor<Bytes>> {
@@ -56,7 +60,7 @@ impl<'a> StreamUploader<'a, Cursor<Bytes>> {
     ///
     /// * `url` - A public accessible url to the media which will be downloaded.
     /// * `access_token` - An API access token used to make requests to the
-    /// Wistia API.
+    ///   Wistia API.
     ///
     /// # Examples
     ///
@@ -71,7 +75,7 @@ impl<'a> StreamUploader<'a, Cursor<Bytes>> {
     /// ```
     pub async fn with_url_and_token(
         url: &str,
-        access_token: &str,
+        access_token: impl Into<AccessToken>,
     ) -> Result<StreamUploader<'a, Cursor<Bytes>>> {
         let stream = stream_reader_from_url(url, None).await?;
         Ok(Self::with_token(access_token).stream(stream))
@@ -95,7 +99,7 @@ impl<'a> StreamUploader<'a, Cursor<Bytes>> {
     /// ```
     pub async fn with_url_and_client(
         url: &str,
-        client: impl Into<Option<Client<tls::HttpsConnector<HttpConnector>>>>,
+        client: impl Into<Option<HttpsClient>>,
     ) -> Result<StreamUploader<'a, Cursor<Bytes>>> {
         let stream = stream_reader_from_url(url, client).await?;
         Self::new(stream)
@@ -120,7 +124,7 @@ impl<'a> StreamUploader<'a, Cursor<Bytes>> {
     /// ```
     pub async fn with_url_and_arc_client(
         url: &str,
-        client: Arc<Client<tls::HttpsConnector<HttpConnector>>>,
+        client: Arc<HttpsClient>,
     ) -> Result<StreamUploader<'a, Cursor<Bytes>>> {
         let stream = stream_reader_from_url_and_arc_client(url, client).await?;
         Self::new(stream)
@@ -176,6 +180,7 @@ impl<'a, R: 'static + Read + Send + Sync> StreamUploader<'a, R> {
             client: UploadClient::from_env()?,
             req: UploadStreamRequest::new(file_name),
             reader: Some(stream),
+            clone_reader: None,
         })
     }
 
@@ -203,6 +208,7 @@ impl<'a, R: 'static + Read + Send + Sync> StreamUploader<'a, R> {
             client: UploadClient::from_env()?,
             req: UploadStreamRequest::new(file_name),
             reader: None,
+            clone_reader: None,
         })
     }
 
@@ -212,7 +218,7 @@ impl<'a, R: 'static + Read + Send + Sync> StreamUploader<'a, R> {
     /// # Arguments
     ///
     /// * `access_token` - An API access token used to make requests to the
-    /// Wistia API.
+    ///   Wistia API.
     ///
     /// # Examples
     ///
@@ -226,21 +232,22 @@ impl<'a, R: 'static + Read + Send + Sync> StreamUploader<'a, R> {
     /// let res = uploader.send()?.await?;
     /// ```
     ///
-    pub fn with_token(access_token: &str) -> Self {
+    pub fn with_token(access_token: impl Into<AccessToken>) -> Self {
         Self {
             client: UploadClient::from_token(access_token),
             req: UploadStreamRequest::new(DEFAULT_FILENAME),
             reader: None,
+            clone_reader: None,
         }
     }
+}
 
+impl<'a, R: 'static + Read + Send + Sync, T: Transport> StreamUploader<'a, R, T> {
     /// Create a `SteamUploader` with a file path and an HTTPS client.
     ///
     /// # Arguments
     ///
     /// * `client` - The HTTPS client (UploadClient) to use for requests.
-    /// Note that the client must support multipart form requests, via a
-    /// `multipart::Body`.
     ///
     /// # Examples
     ///
@@ -255,11 +262,12 @@ impl<'a, R: 'static + Read + Send + Sync> StreamUploader<'a, R> {
     /// let res = uploader.send()?.await?;
     /// ```
     ///
-    pub fn with_client(client: UploadClient<Body>) -> Self {
+    pub fn with_client(client: UploadClient<T>) -> Self {
         Self {
             client,
             req: UploadStreamRequest::new(DEFAULT_FILENAME),
             reader: None,
+            clone_reader: None,
         }
     }
 
@@ -281,8 +289,8 @@ impl<'a, R: 'static + Read + Send + Sync> StreamUploader<'a, R> {
     /// The hashed id of the project to upload media into. If omitted, a new
     /// project will be created and uploaded to. The naming convention used
     /// for such projects is `Uploads_YYYY-MM-DD`.
-    pub fn project_id(mut self, project_id: &'a str) -> Self {
-        self.req.project_id = Some(project_id);
+    pub fn project_id(mut self, project_id: impl Into<ProjectHashedId>) -> Self {
+        self.req.project_id = Some(project_id.into());
         self
     }
 
@@ -314,37 +322,83 @@ impl<'a, R: 'static + Read + Send + Sync> StreamUploader<'a, R> {
     ///
     // noinspection DuplicatedCode
     pub async fn send(self) -> Result<UploadResponse> {
+        // The token is resolved once, rather than for each attempt
+        let token = self.client.access_token().await?;
+
         // Build the query parameters to pass to the Upload API
 
         let params = UploadRequest {
-            access_token: self.client.access_token.as_str(),
+            access_token: self.client.query_token(&token),
             url: None,
-            project_id: self.req.project_id,
+            project_id: self.req.project_id.as_ref().map(ProjectHashedId::as_str),
             name: self.req.name,
             description: None,
             contact_id: self.req.contact_id,
         };
 
-        let url = UploadClient::<Body>::build_url(params)?;
+        let url = self.client.build_url(params)?;
 
-        // Create a request instance and multipart form
-        let req_builder = Request::post(&url);
-        let mut form = Form::default();
+        let file_name = self.req.file_name.to_owned();
+        let description = self.req.description.map(str::to_owned);
+        let req_url = url.clone();
+        let client = self.client.clone();
+        let req_token = token.clone();
 
-        // Add multi-part form fields
+        let make_req = move |reader: R| -> Result<Request<Body>> {
+            // Create a request instance and multipart form
+            let req_builder = client.post(&req_url, &req_token)?;
+            let mut form = Form::default();
 
-        // TODO worth checking for unwrap()?
-        form.add_reader_file("file", self.reader.unwrap(), self.req.file_name);
+            // Add multi-part form fields
 
-        if let Some(description) = self.req.description {
-            form.add_text("description", description);
-        }
+            if let Some(token) = client.form_token(&req_token) {
+                form.add_text("access_token", token);
+            }
+
+            form.add_reader_file("file", reader, file_name.clone());
+
+            if let Some(description) = &description {
+                form.add_text("description", description);
+            }
+
+            // Update a request instance with the multipart Content-Type header
+            // and the payload data, which is converted into a `hyper::Body` so
+            // that the client (and its connection pool) can be shared with the
+            // other APIs.
+            Ok(form.set_body_convert::<Body, multipart::Body>(req_builder)?)
+        };
 
-        // Update a request instance with the multipart Content-Type header
-        // and the payload data.
-        let form = form.set_body::<Body>(req_builder).unwrap();
+        // A stream can only be read once, unless it can be cloned; in which
+        // case, the request is built with a clone of the reader for each
+        // attempt.
+        let (req, retry, replay) = match (self.reader, self.clone_reader) {
+            (Some(reader), Some(clone_reader)) => {
+                let replay = Replay::new(move || make_req(clone_reader(&reader)));
+                (replay.make()?, Retry::Always, Some(replay))
+            }
+            // TODO worth checking for unwrap()?
+            (reader, _) => (make_req(reader.unwrap())?, Retry::Never, None),
+        };
 
         // Send the request
-        self.client.make_request(&url, form).await
+        self.client
+            .send_request(&url, req, Some(&token), retry, replay)
+            .await
+    }
+}
+
+impl<'a, R: 'static + Read + Send + Sync + Clone, T: Transport> StreamUploader<'a, R, T> {
+    /// Retry the upload if it fails due to a rate limit or a server error,
+    /// as per the [`RetryPolicy`] of the client. The stream is cloned for
+    /// each attempt, so this is only available for a stream which can be
+    /// cloned, such as a `Cursor<Bytes>`.
+    ///
+    /// By default, a stream upload is never retried, as the stream can only
+    /// be read once.
+    ///
+    /// [`RetryPolicy`]: crate::RetryPolicy
+    pub fn retry_upload(mut self, retry: bool) -> Self {
+        self.clone_reader = if retry { Some(R::clone) } else { None };
+        self
     }
 }
@@ -1,10 +1,12 @@
 use crate::api::client::UploadClient;
-use crate::constants::UPLOAD_API;
+use crate::api::retry::{Replay, Retry};
+use crate::api::transport::{BoxTransport, Transport};
+use crate::auth::AccessToken;
 use crate::models::*;
 use crate::types::Result;
 
 use hyper::header::CONTENT_TYPE;
-use hyper::{Body, Request};
+use hyper::Body;
 use serde_urlencoded::to_string;
 
 /// Client implementation to upload *URL links* to media files via the
@@ -16,9 +18,10 @@ use serde_urlencoded::to_string;
 /// [Upload API]: https://wistia.com/support/developers/upload-api
 ///
 #[derive(Clone)]
-pub struct UrlUploader<'a, B = Body> {
-    client: UploadClient<B>,
+pub struct UrlUploader<'a, T = BoxTransport> {
+    client: UploadClient<T>,
     req: UploadUrlRequest<'a>,
+    retry: bool,
 }
 
 impl<'a> From<String> for UrlUploader<'a> {
@@ -27,6 +30,7 @@ impl<'a> From<String> for UrlUploader<'a> {
         Self {
             client: UploadClient::from(token),
             req: UploadUrlRequest::default(),
+            retry: false,
         }
     }
 }
@@ -37,6 +41,7 @@ impl<'a> From<&str> for UrlUploader<'a> {
         Self {
             client: UploadClient::from(token),
             req: UploadUrlRequest::default(),
+            retry: false,
         }
     }
 }
@@ -48,7 +53,7 @@ impl<'a> UrlUploader<'a> {
     /// # Arguments
     ///
     /// * `url` - A publicly-accessible URL link to the media file. The link
-    /// will be *form-url encoded* into the request body.
+    ///   will be *form-url encoded* into the request body.
     ///
     pub fn new(url: &'a str) -> Result<Self> {
         Ok(Self {
@@ -57,6 +62,7 @@ impl<'a> UrlUploader<'a> {
                 url,
                 ..Default::default()
             },
+            retry: false,
         })
     }
 
@@ -66,35 +72,39 @@ impl<'a> UrlUploader<'a> {
     /// # Arguments
     ///
     /// * `url` - A publicly-accessible URL link to the media file. The link
-    /// will be *form-url encoded* into the request body.
+    ///   will be *form-url encoded* into the request body.
     /// * `access_token` - An API access token used to make requests to the
-    /// Wistia API.
+    ///   Wistia API.
     ///
-    pub fn with_token(url: &'a str, access_token: &str) -> Self {
+    pub fn with_token(url: &'a str, access_token: impl Into<AccessToken>) -> Self {
         Self {
-            client: UploadClient::from(access_token),
+            client: UploadClient::from_token(access_token),
             req: UploadUrlRequest {
                 url,
                 ..Default::default()
             },
+            retry: false,
         }
     }
+}
 
+impl<'a, T: Transport> UrlUploader<'a, T> {
     /// Create an `UrlUploader` with a URL link and an HTTPS client.
     ///
     /// # Arguments
     ///
     /// * `url` - A publicly-accessible URL link to the media file. The link
-    /// will be *form-url encoded* into the request body.
+    ///   will be *form-url encoded* into the request body.
     /// * `client` - The HTTPS client (UploadClient) to use for requests.
     ///
-    pub fn with_client(url: &'a str, client: UploadClient<Body>) -> Self {
+    pub fn with_client(url: &'a str, client: UploadClient<T>) -> Self {
         Self {
             client,
             req: UploadUrlRequest {
                 url,
                 ..Default::default()
             },
+            retry: false,
         }
     }
 
@@ -112,8 +122,8 @@ impl<'a> UrlUploader<'a> {
     /// The hashed id of the project to upload media into. If omitted, a new
     /// project will be created and uploaded to. The naming convention used
     /// for such projects is `Uploads_YYYY-MM-DD`.
-    pub fn project_id(mut self, project_id: &'a str) -> Self {
-        self.req.project_id = Some(project_id);
+    pub fn project_id(mut self, project_id: impl Into<ProjectHashedId>) -> Self {
+        self.req.project_id = Some(project_id.into());
         self
     }
 
@@ -138,18 +148,33 @@ impl<'a> UrlUploader<'a> {
         self
     }
 
+    /// Retry the upload if it fails due to a rate limit or a server error,
+    /// as per the [`RetryPolicy`] of the client.
+    ///
+    /// Uploads are not idempotent, so by default they are only retried on
+    /// an error when connecting.
+    ///
+    /// [`RetryPolicy`]: crate::RetryPolicy
+    pub fn retry_upload(mut self, retry: bool) -> Self {
+        self.retry = retry;
+        self
+    }
+
     /// Send the Upload URL request (with the *form-url encoded* data) to the
     /// Wistia [Upload API].
     ///
     /// [Upload API]: https://wistia.com/support/developers/upload-api
     ///
     pub async fn send(&self) -> Result<UploadResponse> {
+        // The token is resolved once, rather than for each attempt
+        let token = self.client.access_token().await?;
+
         // Build the query parameters to pass to the Upload API
 
         let params = UploadRequest {
-            access_token: self.client.access_token.as_str(),
+            access_token: self.client.form_token(&token).unwrap_or_default(),
             url: Some(self.req.url),
-            project_id: self.req.project_id,
+            project_id: self.req.project_id.as_ref().map(ProjectHashedId::as_str),
             name: self.req.name,
             description: self.req.description,
             contact_id: self.req.contact_id,
@@ -157,13 +182,41 @@ impl<'a> UrlUploader<'a> {
 
         let url_encoded_data = to_string(params)?;
 
+        // The access token is only in the URL via `UploadAuth::Query`
+        let upload_url = self.client.build_url(UploadRequest {
+            access_token: self.client.query_token(&token),
+            ..Default::default()
+        })?;
+
         // Create a request instance
-        let req = Request::post(UPLOAD_API)
-            // Make sure the server knows the data is `x-www-form-urlencoded`
-            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
-            .body(Body::from(url_encoded_data))?;
+        let replay = {
+            let client = self.client.clone();
+            let upload_url = upload_url.clone();
+            let token = token.clone();
+            Replay::new(move || {
+                Ok(client
+                    .post(&upload_url, &token)?
+                    // Make sure the server knows the data is `x-www-form-urlencoded`
+                    .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+                    .body(Body::from(url_encoded_data.clone()))?)
+            })
+        };
+
+        let retry = if self.retry {
+            Retry::Always
+        } else {
+            Retry::Idempotent
+        };
 
         // Send the request
-        self.client.make_request(UPLOAD_API, req).await
+        self.client
+            .send_request(
+                &upload_url,
+                replay.make()?,
+                Some(&token),
+                retry,
+                Some(replay),
+            )
+            .await
     }
 }
@@ -0,0 +1,304 @@
+use crate::api::builder::{ClientBuilder, RequestOptions};
+use crate::api::client::{UploadAuth, UploadClient};
+use crate::api::data::{DataClient, StatsClient};
+use crate::api::transport::{BoxTransport, Transport};
+#[cfg(feature = "upload-url")]
+use crate::api::upload::UrlUploader;
+#[cfg(feature = "upload-file")]
+use crate::api::upload::{FileUploader, StreamUploader};
+use crate::auth::{AccessToken, TokenProvider};
+use crate::constants::{DATA_API, UPLOAD_API};
+use crate::https::{get_https_client, HttpsClient};
+use crate::models::*;
+use std::fmt::{self, Debug};
+use std::io::Cursor;
+use std::sync::Arc;
+#[cfg(feature = "upload-file")]
+use std::{io::Read, path::Path};
+
+use hyper::body::Bytes;
+use tokio::io::AsyncWrite;
+
+/// A single entry point to the Wistia **Data**, **Upload** and **Stats**
+/// APIs.
+///
+/// A `Wistia` client owns an API access token and *one* HTTPS client, and
+/// the clients and uploaders it hands out all share that HTTPS client -
+/// and so its connection pool. Prefer this when making many requests, such
+/// as a batch of uploads, to avoid a new TLS handshake for each request.
+///
+/// Cloning a `Wistia` client is cheap, and the clones share the same
+/// connection pool.
+///
+/// Like the other clients, it is generic over the [`Transport`] which sends
+/// requests; see [`Wistia::with_transport`].
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use rust_wistia::Wistia;
+///
+/// let wistia = Wistia::from_env()?;
+///
+/// let media = wistia.data().get_media("abc1234567").await?;
+/// let stats = wistia.stats().media(&media.hashed_id).await?;
+///
+/// let res = wistia
+///     .url_uploader("https://example.com/my/video.mp4")
+///     .name("My Video Name")
+///     .send()
+///     .await?;
+/// ```
+#[derive(Clone)]
+pub struct Wistia<T = BoxTransport> {
+    data: DataClient<T>,
+    upload: UploadClient<T>,
+}
+
+impl From<AccessToken> for Wistia {
+    /// Create a new `Wistia` client from an access token
+    fn from(access_token: AccessToken) -> Self {
+        Self::with_https_client(access_token, get_https_client())
+    }
+}
+
+impl From<String> for Wistia {
+    /// Create a new `Wistia` client from an access token
+    fn from(access_token: String) -> Self {
+        Self::from(AccessToken::from(access_token))
+    }
+}
+
+impl From<&str> for Wistia {
+    /// Create a new `Wistia` client from an access token
+    fn from(access_token: &str) -> Self {
+        Self::from(AccessToken::from(access_token))
+    }
+}
+
+impl Wistia {
+    /// Create a new `Wistia` client from an access token
+    pub fn new(access_token: impl Into<AccessToken>) -> Self {
+        Self::from(access_token.into())
+    }
+
+    /// Create a new `Wistia` client from an access token and an existing
+    /// HTTPS client, which is shared by all requests.
+    pub fn with_https_client(access_token: impl Into<AccessToken>, client: HttpsClient) -> Self {
+        Self::from_options(Arc::new(access_token.into()), RequestOptions::new(client))
+    }
+
+    /// Returns a [`ClientBuilder`] to configure a new `Wistia` client, such
+    /// as to set timeouts or a custom User-Agent.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// Initialize a new `Wistia` client from an API access token, assuming
+    /// this is currently set in the environment.
+    ///
+    /// The base URLs of the Data and Upload APIs can also be overridden,
+    /// via the `WISTIA_API_URL` and `WISTIA_UPLOAD_URL` environment variables.
+    pub fn from_env() -> crate::Result<Self> {
+        ClientBuilder::new().build()
+    }
+}
+
+impl<T> Debug for Wistia<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Wistia")
+            .field("data", &self.data)
+            .field("upload", &self.upload)
+            .finish()
+    }
+}
+
+impl<T: Transport> Wistia<T> {
+    /// Create a new `Wistia` client from an access token, which sends
+    /// requests with a custom `transport`, such as a
+    /// [`MockTransport`](crate::mock::MockTransport).
+    ///
+    /// The transport is used as-is, so requests are not retried unless it
+    /// is wrapped in a [`RetryLayer`](crate::RetryLayer).
+    pub fn with_transport(access_token: impl Into<AccessToken>, transport: T) -> Self {
+        Self::from_options(
+            Arc::new(access_token.into()),
+            RequestOptions::with_transport(transport),
+        )
+    }
+
+    /// Create a new `Wistia` client from the options (and transport) which
+    /// are shared by the Data and Upload clients.
+    fn from_options(token: Arc<dyn TokenProvider>, options: RequestOptions<T>) -> Self {
+        Self {
+            data: DataClient::from_parts(token.clone(), DATA_API.to_owned(), options.clone()),
+            upload: UploadClient::from_parts(token, UPLOAD_API.to_owned(), options),
+        }
+    }
+
+    /// Create a new `Wistia` client from a Data and Upload client, which
+    /// should share the same transport.
+    pub(crate) fn from_clients(data: DataClient<T>, upload: UploadClient<T>) -> Self {
+        Self { data, upload }
+    }
+
+    /// Sets the base URL of the Wistia Data API (default: `https://api.wistia.com/v1`).
+    ///
+    /// See [`DataClient::with_base_url`] for more info.
+    pub fn with_base_url(mut self, base_url: &str) -> crate::Result<Self> {
+        self.data = self.data.with_base_url(base_url)?;
+        Ok(self)
+    }
+
+    /// Sets the base URL of the Wistia Upload API (default: `https://upload.wistia.com`).
+    ///
+    /// See [`UploadClient::with_upload_url`] for more info.
+    pub fn with_upload_url(mut self, upload_url: &str) -> crate::Result<Self> {
+        self.upload = self.upload.with_upload_url(upload_url)?;
+        Ok(self)
+    }
+
+    /// Sets how the access token is sent with each request to the Upload API
+    /// (default: as a bearer token in the `Authorization` header).
+    pub fn with_upload_auth(mut self, auth: UploadAuth) -> Self {
+        self.upload = self.upload.with_auth(auth);
+        self
+    }
+
+    /// Sets the [`TokenProvider`] which is consulted for the access token on
+    /// each call to the Data and Upload APIs.
+    pub fn with_token_provider(mut self, provider: impl TokenProvider) -> Self {
+        let provider: Arc<dyn TokenProvider> = Arc::new(provider);
+        self.data = self.data.with_token_provider(provider.clone());
+        self.upload = self.upload.with_token_provider(provider);
+        self
+    }
+
+    /// Returns a clone of the client which authenticates with another access
+    /// `token`, such as for calls on behalf of another account. The clone
+    /// shares the same connection pool and other options, while any
+    /// [`RateLimit`](crate::RateLimit) applies to the new token on its own.
+    pub fn for_token(&self, token: impl Into<AccessToken>) -> Self {
+        let token = token.into();
+        Self {
+            data: self.data.for_token(token.clone()),
+            upload: self.upload.for_token(token),
+        }
+    }
+
+    /// Returns the shared transport, which sends each request.
+    pub fn transport(&self) -> &T {
+        self.data.transport()
+    }
+
+    /// Returns a client for the Wistia [Data API].
+    ///
+    /// [Data API]: https://wistia.com/support/developers/data-api
+    pub fn data(&self) -> &DataClient<T> {
+        &self.data
+    }
+
+    /// Returns a client for the Wistia [Upload API].
+    ///
+    /// [Upload API]: https://wistia.com/support/developers/upload-api
+    pub fn upload(&self) -> &UploadClient<T> {
+        &self.upload
+    }
+
+    /// Returns a client for the Wistia [Stats API].
+    ///
+    /// [Stats API]: https://wistia.com/support/developers/stats-api
+    pub fn stats(&self) -> StatsClient<T> {
+        StatsClient::from(self.data.clone())
+    }
+
+    /// Returns an uploader for a publicly-accessible URL link to a media file.
+    #[cfg(feature = "upload-url")]
+    pub fn url_uploader<'a>(&self, url: &'a str) -> UrlUploader<'a, T> {
+        UrlUploader::with_client(url, self.upload.clone())
+    }
+
+    /// Returns an uploader for a media file on disk.
+    #[cfg(feature = "upload-file")]
+    pub fn file_uploader<'a, P: AsRef<Path> + Debug>(
+        &self,
+        file_path: P,
+    ) -> FileUploader<'a, P, T> {
+        FileUploader::with_client(file_path, self.upload.clone())
+    }
+
+    /// Returns an uploader for a readable file-like *stream* object.
+    #[cfg(feature = "upload-file")]
+    pub fn stream_uploader<'a, R: 'static + Read + Send + Sync>(
+        &self,
+        stream: R,
+    ) -> StreamUploader<'a, R, T> {
+        StreamUploader::with_client(self.upload.clone()).stream(stream)
+    }
+
+    /// Download an [Asset URL] for a media from Wistia.
+    ///
+    /// See [`DataClient::download_asset`] for more info.
+    ///
+    /// [Asset URL]: https://wistia.com/support/developers/asset-urls
+    pub async fn download_asset(&self, req: DownloadAssetRequest) -> crate::Result<Cursor<Bytes>> {
+        self.data.download_asset(req).await
+    }
+
+    /// Download an [Asset URL] for a media from Wistia, and write it to
+    /// `writer` as it is received.
+    ///
+    /// See [`DataClient::download_asset_to`] for more info.
+    ///
+    /// [Asset URL]: https://wistia.com/support/developers/asset-urls
+    pub async fn download_asset_to<W>(
+        &self,
+        req: DownloadAssetRequest,
+        writer: &mut W,
+    ) -> crate::Result<u64>
+    where
+        W: AsyncWrite + Unpin + Send + ?Sized,
+    {
+        self.data.download_asset_to(req, writer).await
+    }
+
+    /// Download the contents of a publicly accessible `url`, such as a
+    /// media file to then upload via [`Wistia::stream_uploader`].
+    pub async fn download_url(&self, url: &str) -> crate::Result<Cursor<Bytes>> {
+        self.data.download_url(url).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_with_base_url() {
+        let wistia = Wistia::from("my-token")
+            .with_base_url("http://localhost:8080/v1")
+            .unwrap();
+
+        let token = wistia.data().access_token().await.unwrap();
+        assert_eq!(token.expose_secret(), "my-token");
+        assert_eq!(wistia.data().base_url(), "http://localhost:8080/v1");
+        let token = wistia.upload().access_token().await.unwrap();
+        assert_eq!(token.expose_secret(), "my-token");
+        assert_eq!(wistia.upload().upload_url(), UPLOAD_API);
+    }
+
+    #[tokio::test]
+    async fn test_for_token() {
+        let wistia = Wistia::from("my-token");
+        let tenant = wistia.for_token("tenant-token");
+
+        let token = tenant.data().access_token().await.unwrap();
+        assert_eq!(token.expose_secret(), "tenant-token");
+        let token = tenant.upload().access_token().await.unwrap();
+        assert_eq!(token.expose_secret(), "tenant-token");
+
+        // The original client is unchanged
+        let token = wistia.data().access_token().await.unwrap();
+        assert_eq!(token.expose_secret(), "my-token");
+    }
+}
@@ -0,0 +1,129 @@
+//! Authentication helper utilities
+
+mod provider;
+
+pub use provider::{EnvToken, FileToken, FnToken, TokenFuture, TokenProvider};
+
+use crate::types::Result;
+use crate::utils::REDACTED;
+
+use std::fmt;
+
+use hyper::header::HeaderValue;
+use hyper::http;
+use zeroize::{Zeroize, Zeroizing};
+
+/// An [API access token] used to authenticate requests to the Wistia API.
+///
+/// The token is masked in its `Debug` and `Display` output, and is zeroized
+/// (overwritten in memory) when dropped. It can only be read via
+/// [`AccessToken::expose_secret`].
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_wistia::AccessToken;
+///
+/// let token = AccessToken::new("my-token");
+///
+/// assert_eq!(format!("{token:?}"), "AccessToken(REDACTED)");
+/// assert_eq!(token.expose_secret(), "my-token");
+/// ```
+///
+/// [API access token]: https://wistia.com/support/developers/data-api#getting-started
+#[derive(Clone)]
+pub struct AccessToken(String);
+
+impl AccessToken {
+    /// Create a new `AccessToken`.
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+
+    /// Returns the token itself, such as to send it in a request.
+    ///
+    /// Take care not to log (or otherwise leak) the returned value.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the value of the `AUTHORIZATION` header for a request, which
+    /// is marked as sensitive.
+    pub(crate) fn bearer_header(&self) -> Result<HeaderValue> {
+        let bearer_token = Zeroizing::new(auth_token(&self.0));
+
+        let mut value = HeaderValue::from_str(&bearer_token).map_err(http::Error::from)?;
+        value.set_sensitive(true);
+
+        Ok(value)
+    }
+}
+
+impl From<&str> for AccessToken {
+    fn from(token: &str) -> Self {
+        Self::new(token)
+    }
+}
+
+impl From<String> for AccessToken {
+    fn from(token: String) -> Self {
+        Self(token)
+    }
+}
+
+impl From<&String> for AccessToken {
+    fn from(token: &String) -> Self {
+        Self::new(token.as_str())
+    }
+}
+
+impl fmt::Debug for AccessToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AccessToken")
+            .field(&format_args!("{REDACTED}"))
+            .finish()
+    }
+}
+
+impl fmt::Display for AccessToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl Drop for AccessToken {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Returns the value to set in the `AUTHORIZATION` header for a request.
+pub fn auth_token(token: &str) -> String {
+    // "Bearer ".len() == 7
+    let mut bearer_token = String::with_capacity(7 + token.len());
+    bearer_token.push_str("Bearer ");
+    bearer_token.push_str(token);
+
+    bearer_token
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_token() {
+        let token = AccessToken::from("my-token");
+
+        assert_eq!(token.to_string(), "REDACTED");
+        assert_eq!(format!("{token:?}"), "AccessToken(REDACTED)");
+        assert_eq!(
+            format!("{:?}", Some(token.clone())),
+            "Some(AccessToken(REDACTED))"
+        );
+
+        let header = token.bearer_header().unwrap();
+        assert_eq!(header, "Bearer my-token");
+        assert!(header.is_sensitive());
+    }
+}
@@ -0,0 +1,240 @@
+//! Sources of the access token, which are consulted for each request so that
+//! a token can be rotated without rebuilding the clients.
+//!
+use crate::auth::AccessToken;
+use crate::constants::ENV_VAR_NAME;
+use crate::types::Result;
+use crate::RustWistiaError;
+
+use std::env::var;
+use std::fmt;
+use std::fs;
+use std::future::{ready, Future};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use zeroize::Zeroizing;
+
+/// The future returned by a [`TokenProvider`].
+pub type TokenFuture<'a> = Pin<Box<dyn Future<Output = Result<AccessToken>> + Send + 'a>>;
+
+/// A source of the [`AccessToken`] which authenticates requests, which the
+/// [`DataClient`] and [`UploadClient`] consult for each call to the API.
+///
+/// An `AccessToken` is itself a (static) provider; see [`EnvToken`],
+/// [`FileToken`] and [`FnToken`] for a token which can change.
+///
+/// [`DataClient`]: crate::DataClient
+/// [`UploadClient`]: crate::UploadClient
+pub trait TokenProvider: Send + Sync + 'static {
+    /// Returns the current access token.
+    fn token(&self) -> TokenFuture<'_>;
+}
+
+impl TokenProvider for AccessToken {
+    fn token(&self) -> TokenFuture<'_> {
+        Box::pin(ready(Ok(self.clone())))
+    }
+}
+
+impl<P: TokenProvider + ?Sized> TokenProvider for Arc<P> {
+    fn token(&self) -> TokenFuture<'_> {
+        (**self).token()
+    }
+}
+
+/// Reads the token from an environment variable (by default,
+/// `WISTIA_API_TOKEN`) for each call.
+#[derive(Debug, Clone)]
+pub struct EnvToken {
+    name: String,
+}
+
+impl EnvToken {
+    /// Read the token from the environment variable `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+impl Default for EnvToken {
+    fn default() -> Self {
+        Self::new(ENV_VAR_NAME)
+    }
+}
+
+impl TokenProvider for EnvToken {
+    fn token(&self) -> TokenFuture<'_> {
+        let token =
+            var(&self.name)
+                .map(AccessToken::from)
+                .map_err(|_| RustWistiaError::EnvVarNotFound {
+                    name: self.name.clone(),
+                });
+
+        Box::pin(ready(token))
+    }
+}
+
+/// Reads the token from a file, such as a mounted secret, which is read
+/// again once the [refresh interval] has passed. Any surrounding whitespace
+/// is trimmed.
+///
+/// The file is read on the calling task, which is brief for a small file,
+/// but is then cached so that most requests don't touch the file system.
+///
+/// [refresh interval]: FileToken::refresh_interval
+pub struct FileToken {
+    path: PathBuf,
+    refresh_interval: Duration,
+    /// The token last read from the file, along with when it was read.
+    cache: Mutex<Option<(Instant, AccessToken)>>,
+}
+
+impl FileToken {
+    /// The default interval after which the file is read again.
+    pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+    /// Read the token from the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            refresh_interval: Self::DEFAULT_REFRESH_INTERVAL,
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Sets the interval after which the file is read again, so that a
+    /// rotated token is picked up (default: 10 seconds). With
+    /// `Duration::ZERO`, the file is read for each call.
+    pub fn refresh_interval(mut self, interval: Duration) -> Self {
+        self.refresh_interval = interval;
+        self
+    }
+
+    /// Returns the path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read(&self) -> Result<AccessToken> {
+        let mut cache = self.cache.lock().unwrap();
+
+        if let Some((read_at, token)) = &*cache {
+            if read_at.elapsed() < self.refresh_interval {
+                return Ok(token.clone());
+            }
+        }
+
+        // The contents are read in full, rather than checking the
+        // modification time or size, which may not change on a rotation.
+        let contents = Zeroizing::new(fs::read_to_string(&self.path)?);
+        let token = AccessToken::new(contents.trim());
+        *cache = Some((Instant::now(), token.clone()));
+
+        Ok(token)
+    }
+}
+
+impl fmt::Debug for FileToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileToken")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TokenProvider for FileToken {
+    fn token(&self) -> TokenFuture<'_> {
+        Box::pin(ready(self.read()))
+    }
+}
+
+/// Calls an async function for the token, such as to fetch it from a
+/// secrets manager; the function is responsible for any caching.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_wistia::auth::FnToken;
+/// use rust_wistia::{AccessToken, DataClient};
+///
+/// let provider = FnToken::new(|| async {
+///     // e.g. fetch the token from a secrets manager
+///     Ok(AccessToken::new("my-token"))
+/// });
+///
+/// let client = DataClient::builder().token_provider(provider).build_data();
+/// ```
+pub struct FnToken<F>(F);
+
+impl<F, Fut> FnToken<F>
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<AccessToken>> + Send + 'static,
+{
+    /// Call `f` for the token.
+    pub fn new(f: F) -> Self {
+        Self(f)
+    }
+}
+
+impl<F> fmt::Debug for FnToken<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FnToken")
+    }
+}
+
+impl<F, Fut> TokenProvider for FnToken<F>
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<AccessToken>> + Send + 'static,
+{
+    fn token(&self) -> TokenFuture<'_> {
+        Box::pin((self.0)())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_token() {
+        let path = std::env::temp_dir().join(format!("rust-wistia-token-{}", std::process::id()));
+        fs::write(&path, "token-1\n").unwrap();
+
+        let provider = FileToken::new(&path);
+        assert_eq!(provider.token().await.unwrap().expose_secret(), "token-1");
+
+        // The cached token is used until the refresh interval has passed
+        fs::write(&path, "rotated-token-2\n").unwrap();
+        assert_eq!(provider.token().await.unwrap().expose_secret(), "token-1");
+
+        let provider = provider.refresh_interval(Duration::ZERO);
+        assert_eq!(
+            provider.token().await.unwrap().expose_secret(),
+            "rotated-token-2"
+        );
+
+        // A token of the same length is also picked up
+        fs::write(&path, "rotated-token-3\n").unwrap();
+        assert_eq!(
+            provider.token().await.unwrap().expose_secret(),
+            "rotated-token-3"
+        );
+
+        fs::remove_file(&path).unwrap();
+        assert!(provider.token().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_env_token() {
+        let provider = EnvToken::new("RUST_WISTIA_TEST_TOKEN_NOT_SET");
+        let err = provider.token().await.unwrap_err();
+
+        assert!(matches!(err, RustWistiaError::EnvVarNotFound { .. }));
+    }
+}
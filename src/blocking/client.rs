@@ -0,0 +1,223 @@
+use crate::auth::AccessToken;
+use crate::blocking::block_on;
+use crate::models::*;
+use crate::types::Result;
+use crate::ClientBuilder;
+use crate::{BoxTransport, Transport};
+
+use std::io::Cursor;
+
+use hyper::body::Bytes;
+use hyper::Method;
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+
+/// A *blocking* client to make requests to the Wistia **[Data API]**, which
+/// mirrors the async [`crate::DataClient`].
+///
+/// To configure the client, such as to set timeouts, build an async client
+/// with a [`ClientBuilder`] and convert it with `From`.
+///
+/// [Data API]: https://wistia.com/support/developers/data-api
+///
+#[derive(Clone)]
+pub struct DataClient<T = BoxTransport> {
+    inner: crate::DataClient<T>,
+}
+
+impl<T> From<crate::DataClient<T>> for DataClient<T> {
+    /// Create a blocking `DataClient` from an async client
+    fn from(inner: crate::DataClient<T>) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<&str> for DataClient {
+    /// Create a new `DataClient` from an access token
+    fn from(token: &str) -> Self {
+        Self::from(crate::DataClient::from(token))
+    }
+}
+
+impl From<String> for DataClient {
+    /// Create a new `DataClient` from an access token
+    fn from(token: String) -> Self {
+        Self::from(crate::DataClient::from(token))
+    }
+}
+
+impl DataClient {
+    /// Create a new `DataClient` from an access token
+    pub fn new(access_token: impl Into<AccessToken>) -> Self {
+        Self::from(crate::DataClient::new(access_token))
+    }
+
+    /// Returns a [`ClientBuilder`] to configure a new client; convert the
+    /// async client it builds with `From`.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// Create a new `DataClient` with the access token retrieved from the
+    /// environment.
+    pub fn from_env() -> Result<Self> {
+        crate::DataClient::from_env().map(Self::from)
+    }
+}
+
+impl<T: Transport> DataClient<T> {
+    /// Create a new `DataClient` from an access token, which sends requests
+    /// with a custom `transport`.
+    pub fn with_transport(access_token: impl Into<AccessToken>, transport: T) -> Self {
+        Self::from(crate::DataClient::with_transport(access_token, transport))
+    }
+
+    /// Returns the async client which sends the requests.
+    pub fn as_async(&self) -> &crate::DataClient<T> {
+        &self.inner
+    }
+
+    /// Returns the transport which sends each request.
+    pub fn transport(&self) -> &T {
+        self.inner.transport()
+    }
+
+    /// Sets the base URL of the Wistia Data API (default: `https://api.wistia.com/v1`).
+    pub fn with_base_url(self, base_url: &str) -> Result<Self> {
+        self.inner.with_base_url(base_url).map(Self::from)
+    }
+
+    /// Returns the base URL of the Wistia Data API.
+    pub fn base_url(&self) -> &str {
+        self.inner.base_url()
+    }
+
+    /// Build the URL to a `path` of the Wistia Data API.
+    pub fn api_url(&self, path: &str) -> String {
+        self.inner.api_url(path)
+    }
+
+    /// Download an asset for a Wistia media, and return the *bytes* content.
+    pub fn download_asset(&self, req: DownloadAssetRequest) -> Result<Cursor<Bytes>> {
+        block_on(self.inner.download_asset(req))
+    }
+
+    /// Retrieve info on a Wistia media.
+    pub fn get_media(&self, video_id: impl Into<MediaHashedId>) -> Result<Media> {
+        block_on(self.inner.get_media(video_id))
+    }
+
+    /// Update info on a Wistia media.
+    pub fn update_media(&self, video: UpdateMediaRequest) -> Result<MediaInfo> {
+        block_on(self.inner.update_media(video))
+    }
+
+    /// Make a GET request to the Wistia Data API
+    pub fn get<R: DeserializeOwned>(&self, url: &str) -> Result<R> {
+        block_on(self.inner.get(url))
+    }
+
+    /// Make a POST request to the Wistia Data API, with included *query parameters*
+    pub fn post<B: Serialize, R: DeserializeOwned>(&self, url: &str, params: B) -> Result<R> {
+        block_on(self.inner.post(url, params))
+    }
+
+    /// Make a POST request to the Wistia Data API, with a JSON body
+    pub fn post_with_body<B: Serialize, R: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: B,
+    ) -> Result<R> {
+        block_on(self.inner.post_with_body(url, body))
+    }
+
+    /// Make a POST request to the Wistia Data API, with a *form-url encoded* body
+    pub fn post_form<B: Serialize, R: DeserializeOwned>(&self, url: &str, body: B) -> Result<R> {
+        block_on(self.inner.post_form(url, body))
+    }
+
+    /// Make a PUT request to the Wistia Data API, with included *query parameters*
+    pub fn put<B: Serialize, R: DeserializeOwned>(&self, url: &str, params: B) -> Result<R> {
+        block_on(self.inner.put(url, params))
+    }
+
+    /// Make a PUT request to the Wistia Data API, with a JSON body
+    pub fn put_with_body<B: Serialize, R: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: B,
+    ) -> Result<R> {
+        block_on(self.inner.put_with_body(url, body))
+    }
+
+    /// Make a PUT request to the Wistia Data API, with a *form-url encoded* body
+    pub fn put_form<B: Serialize, R: DeserializeOwned>(&self, url: &str, body: B) -> Result<R> {
+        block_on(self.inner.put_form(url, body))
+    }
+
+    /// Make a PATCH request to the Wistia Data API, with included *query parameters*
+    pub fn patch<B: Serialize, R: DeserializeOwned>(&self, url: &str, params: B) -> Result<R> {
+        block_on(self.inner.patch(url, params))
+    }
+
+    /// Make a PATCH request to the Wistia Data API, with a JSON body
+    pub fn patch_with_body<B: Serialize, R: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: B,
+    ) -> Result<R> {
+        block_on(self.inner.patch_with_body(url, body))
+    }
+
+    /// Make a PATCH request to the Wistia Data API, with a *form-url encoded* body
+    pub fn patch_form<B: Serialize, R: DeserializeOwned>(&self, url: &str, body: B) -> Result<R> {
+        block_on(self.inner.patch_form(url, body))
+    }
+
+    /// Make a DELETE request to the Wistia Data API
+    pub fn delete<R: DeserializeOwned>(&self, url: &str) -> Result<R> {
+        block_on(self.inner.delete(url))
+    }
+
+    /// Make a request to the Wistia Data API, with the specified HTTP
+    /// `method` and request `body`.
+    pub fn request<R: DeserializeOwned>(
+        &self,
+        method: Method,
+        url: &str,
+        body: RequestBody,
+    ) -> Result<R> {
+        block_on(self.inner.request(method, url, body))
+    }
+
+    /// Make a GET request to the Wistia Data API, and return the *raw* response
+    pub fn get_raw(&self, url: &str) -> Result<RawResponse> {
+        block_on(self.inner.get_raw(url))
+    }
+
+    /// Make a POST request to the Wistia Data API, and return the *raw* response
+    pub fn post_raw(&self, url: &str, body: RequestBody) -> Result<RawResponse> {
+        block_on(self.inner.post_raw(url, body))
+    }
+
+    /// Make a PUT request to the Wistia Data API, and return the *raw* response
+    pub fn put_raw(&self, url: &str, body: RequestBody) -> Result<RawResponse> {
+        block_on(self.inner.put_raw(url, body))
+    }
+
+    /// Make a PATCH request to the Wistia Data API, and return the *raw* response
+    pub fn patch_raw(&self, url: &str, body: RequestBody) -> Result<RawResponse> {
+        block_on(self.inner.patch_raw(url, body))
+    }
+
+    /// Make a DELETE request to the Wistia Data API, and return the *raw* response
+    pub fn delete_raw(&self, url: &str) -> Result<RawResponse> {
+        block_on(self.inner.delete_raw(url))
+    }
+
+    /// Make a request to the Wistia Data API, with the specified HTTP
+    /// `method` and request `body`, and return the *raw* response.
+    pub fn request_raw(&self, method: Method, url: &str, body: RequestBody) -> Result<RawResponse> {
+        block_on(self.inner.request_raw(method, url, body))
+    }
+}
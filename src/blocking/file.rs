@@ -0,0 +1,80 @@
+use crate::auth::AccessToken;
+use crate::blocking::block_on;
+use crate::models::*;
+use crate::types::Result;
+use crate::UploadClient;
+use crate::{BoxTransport, Transport};
+
+use std::fmt::Debug;
+use std::path::Path;
+
+/// A *blocking* client to upload *files* and *videos* via the Wistia
+/// **[Upload API]**, which mirrors the async [`crate::FileUploader`].
+///
+/// [Upload API]: https://wistia.com/support/developers/upload-api
+///
+#[derive(Clone)]
+pub struct FileUploader<'a, P: AsRef<Path>, T = BoxTransport> {
+    inner: crate::FileUploader<'a, P, T>,
+}
+
+impl<'a, P: AsRef<Path>, T> From<crate::FileUploader<'a, P, T>> for FileUploader<'a, P, T> {
+    /// Create a blocking `FileUploader` from an async uploader
+    fn from(inner: crate::FileUploader<'a, P, T>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a, P: AsRef<Path> + Debug> FileUploader<'a, P> {
+    /// Create a `FileUploader` with a new HTTPS client, with the access token
+    /// retrieved from the environment.
+    pub fn new(file_path: P) -> Result<Self> {
+        crate::FileUploader::new(file_path).map(Self::from)
+    }
+
+    /// Create a `FileUploader` with a new HTTPS client and a Wistia access
+    /// token.
+    pub fn with_token(file_path: P, access_token: impl Into<AccessToken>) -> Self {
+        Self::from(crate::FileUploader::with_token(file_path, access_token))
+    }
+}
+
+impl<'a, P: AsRef<Path> + Debug, T: Transport> FileUploader<'a, P, T> {
+    /// Create a `FileUploader` with a file path and an upload client.
+    pub fn with_client(file_path: P, client: UploadClient<T>) -> Self {
+        Self::from(crate::FileUploader::with_client(file_path, client))
+    }
+
+    /// The hashed id of the project to upload media into.
+    pub fn project_id(self, project_id: impl Into<ProjectHashedId>) -> Self {
+        Self::from(self.inner.project_id(project_id))
+    }
+
+    /// A display name to use for the media in Wistia.
+    pub fn name(self, name: &'a str) -> Self {
+        Self::from(self.inner.name(name))
+    }
+
+    /// A description to use for the media in Wistia.
+    pub fn description(self, description: &'a str) -> Self {
+        Self::from(self.inner.description(description))
+    }
+
+    /// A Wistia contact id, an integer value.
+    pub fn contact_id(self, contact_id: &'a str) -> Self {
+        Self::from(self.inner.contact_id(contact_id))
+    }
+
+    /// Retry the upload if it fails due to a rate limit or a server error.
+    pub fn retry_upload(self, retry: bool) -> Self {
+        Self::from(self.inner.retry_upload(retry))
+    }
+
+    /// Send the Upload File request to the Wistia [Upload API], blocking
+    /// until it completes.
+    ///
+    /// [Upload API]: https://wistia.com/support/developers/upload-api
+    pub fn send(&self) -> Result<UploadResponse> {
+        block_on(self.inner.send())
+    }
+}
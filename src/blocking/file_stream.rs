@@ -0,0 +1,134 @@
+use crate::auth::AccessToken;
+use crate::blocking::block_on;
+use crate::https::HttpsClient;
+use crate::models::*;
+use crate::types::Result;
+use crate::UploadClient;
+use crate::{BoxTransport, Transport};
+
+use std::io::{Cursor, Read};
+use std::sync::Arc;
+
+use hyper::body::Bytes;
+
+/// A *blocking* client to upload *streams* (file-like objects) and *videos*
+/// via the Wistia **[Upload API]**, which mirrors the async
+/// [`crate::StreamUploader`].
+///
+/// [Upload API]: https://wistia.com/support/developers/upload-api
+///
+#[derive(Clone)]
+pub struct StreamUploader<'a, R: 'static + Read + Send + Sync, T = BoxTransport> {
+    inner: crate::StreamUploader<'a, R, T>,
+}
+
+impl<'a, R: 'static + Read + Send + Sync, T> From<crate::StreamUploader<'a, R, T>>
+    for StreamUploader<'a, R, T>
+{
+    /// Create a blocking `StreamUploader` from an async uploader
+    fn from(inner: crate::StreamUploader<'a, R, T>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a> StreamUploader<'a, Cursor<Bytes>> {
+    /// Create a new `StreamUploader` which uses the *bytes* content downloaded
+    /// from a publicly accessible **url**, blocking until it is downloaded.
+    pub fn with_url(url: &str) -> Result<Self> {
+        block_on(crate::StreamUploader::with_url(url)).map(Self::from)
+    }
+
+    /// Create a new `StreamUploader` which uses the *bytes* content downloaded
+    /// from a publicly accessible **url**, with a Wistia access token.
+    pub fn with_url_and_token(url: &str, access_token: impl Into<AccessToken>) -> Result<Self> {
+        block_on(crate::StreamUploader::with_url_and_token(url, access_token)).map(Self::from)
+    }
+
+    /// Create a new `StreamUploader` which uses the *bytes* content downloaded
+    /// from a publicly accessible **url**, with an optional HTTPS client to
+    /// download the media.
+    pub fn with_url_and_client(url: &str, client: impl Into<Option<HttpsClient>>) -> Result<Self> {
+        block_on(crate::StreamUploader::with_url_and_client(url, client)).map(Self::from)
+    }
+
+    /// Create a new `StreamUploader` which uses the *bytes* content downloaded
+    /// from a publicly accessible **url**, with a shared HTTPS client to
+    /// download the media.
+    pub fn with_url_and_arc_client(url: &str, client: Arc<HttpsClient>) -> Result<Self> {
+        block_on(crate::StreamUploader::with_url_and_arc_client(url, client)).map(Self::from)
+    }
+}
+
+impl<'a, R: 'static + Read + Send + Sync> StreamUploader<'a, R> {
+    /// Create a `StreamUploader` with a new HTTPS client, with the access
+    /// token retrieved from the environment.
+    pub fn new(stream: R) -> Result<Self> {
+        crate::StreamUploader::new(stream).map(Self::from)
+    }
+
+    /// Create a `StreamUploader` with a new HTTPS client and a file name,
+    /// with the access token retrieved from the environment.
+    pub fn with_stream_and_filename(stream: R, file_name: &'a str) -> Result<Self> {
+        crate::StreamUploader::with_stream_and_filename(stream, file_name).map(Self::from)
+    }
+
+    /// Create a `StreamUploader` with a new HTTPS client and a file name,
+    /// with the access token retrieved from the environment.
+    pub fn with_filename(file_name: &'a str) -> Result<Self> {
+        crate::StreamUploader::with_filename(file_name).map(Self::from)
+    }
+
+    /// Create a `StreamUploader` with a new HTTPS client and a Wistia access
+    /// token.
+    pub fn with_token(access_token: impl Into<AccessToken>) -> Self {
+        Self::from(crate::StreamUploader::with_token(access_token))
+    }
+}
+
+impl<'a, R: 'static + Read + Send + Sync, T: Transport> StreamUploader<'a, R, T> {
+    /// Create a `StreamUploader` with an upload client.
+    pub fn with_client(client: UploadClient<T>) -> Self {
+        Self::from(crate::StreamUploader::with_client(client))
+    }
+
+    /// Set the reader stream which will be used to upload the media.
+    pub fn stream(self, stream: R) -> Self {
+        Self::from(self.inner.stream(stream))
+    }
+
+    /// The hashed id of the project to upload media into.
+    pub fn project_id(self, project_id: impl Into<ProjectHashedId>) -> Self {
+        Self::from(self.inner.project_id(project_id))
+    }
+
+    /// A display name to use for the media in Wistia.
+    pub fn name(self, name: &'a str) -> Self {
+        Self::from(self.inner.name(name))
+    }
+
+    /// A description to use for the media in Wistia.
+    pub fn description(self, description: &'a str) -> Self {
+        Self::from(self.inner.description(description))
+    }
+
+    /// A Wistia contact id, an integer value.
+    pub fn contact_id(self, contact_id: &'a str) -> Self {
+        Self::from(self.inner.contact_id(contact_id))
+    }
+
+    /// Send the Upload Stream request to the Wistia [Upload API], blocking
+    /// until it completes.
+    ///
+    /// [Upload API]: https://wistia.com/support/developers/upload-api
+    pub fn send(self) -> Result<UploadResponse> {
+        block_on(self.inner.send())
+    }
+}
+
+impl<'a, R: 'static + Read + Send + Sync + Clone, T: Transport> StreamUploader<'a, R, T> {
+    /// Retry the upload if it fails due to a rate limit or a server error;
+    /// the stream is cloned for each attempt.
+    pub fn retry_upload(self, retry: bool) -> Self {
+        Self::from(self.inner.retry_upload(retry))
+    }
+}
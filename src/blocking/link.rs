@@ -0,0 +1,96 @@
+use crate::auth::AccessToken;
+use crate::blocking::block_on;
+use crate::models::*;
+use crate::types::Result;
+use crate::UploadClient;
+use crate::{BoxTransport, Transport};
+
+/// A *blocking* client to upload *URL links* to media files via the Wistia
+/// **[Upload API]**, which mirrors the async [`crate::UrlUploader`].
+///
+/// [Upload API]: https://wistia.com/support/developers/upload-api
+///
+#[derive(Clone)]
+pub struct UrlUploader<'a, T = BoxTransport> {
+    inner: crate::UrlUploader<'a, T>,
+}
+
+impl<'a, T> From<crate::UrlUploader<'a, T>> for UrlUploader<'a, T> {
+    /// Create a blocking `UrlUploader` from an async uploader
+    fn from(inner: crate::UrlUploader<'a, T>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a> From<String> for UrlUploader<'a> {
+    /// Create a new `UrlUploader` from an access token
+    fn from(token: String) -> Self {
+        Self::from(crate::UrlUploader::from(token))
+    }
+}
+
+impl<'a> From<&str> for UrlUploader<'a> {
+    /// Create a new `UrlUploader` from an access token
+    fn from(token: &str) -> Self {
+        Self::from(crate::UrlUploader::from(token))
+    }
+}
+
+impl<'a> UrlUploader<'a> {
+    /// Create an `UrlUploader` with a new HTTPS client, with the access token
+    /// retrieved from the environment.
+    pub fn new(url: &'a str) -> Result<Self> {
+        crate::UrlUploader::new(url).map(Self::from)
+    }
+
+    /// Create an `UrlUploader` with a new HTTPS client and a Wistia access
+    /// token.
+    pub fn with_token(url: &'a str, access_token: impl Into<AccessToken>) -> Self {
+        Self::from(crate::UrlUploader::with_token(url, access_token))
+    }
+}
+
+impl<'a, T: Transport> UrlUploader<'a, T> {
+    /// Create an `UrlUploader` with a URL link and an upload client.
+    pub fn with_client(url: &'a str, client: UploadClient<T>) -> Self {
+        Self::from(crate::UrlUploader::with_client(url, client))
+    }
+
+    /// Set the publicly-accessible URL link to the media file.
+    pub fn url(self, url: &'a str) -> Self {
+        Self::from(self.inner.url(url))
+    }
+
+    /// The hashed id of the project to upload media into.
+    pub fn project_id(self, project_id: impl Into<ProjectHashedId>) -> Self {
+        Self::from(self.inner.project_id(project_id))
+    }
+
+    /// A display name to use for the media in Wistia.
+    pub fn name(self, name: &'a str) -> Self {
+        Self::from(self.inner.name(name))
+    }
+
+    /// A description to use for the media in Wistia.
+    pub fn description(self, description: &'a str) -> Self {
+        Self::from(self.inner.description(description))
+    }
+
+    /// A Wistia contact id, an integer value.
+    pub fn contact_id(self, contact_id: &'a str) -> Self {
+        Self::from(self.inner.contact_id(contact_id))
+    }
+
+    /// Retry the upload if it fails due to a rate limit or a server error.
+    pub fn retry_upload(self, retry: bool) -> Self {
+        Self::from(self.inner.retry_upload(retry))
+    }
+
+    /// Send the Upload URL request to the Wistia [Upload API], blocking until
+    /// it completes.
+    ///
+    /// [Upload API]: https://wistia.com/support/developers/upload-api
+    pub fn send(&self) -> Result<UploadResponse> {
+        block_on(self.inner.send())
+    }
+}
@@ -0,0 +1,66 @@
+//! A *blocking* API for the Wistia clients, for use in synchronous code such
+//! as CLIs and build scripts.
+//!
+//! The clients and uploaders here mirror the async ones, and each method
+//! blocks the calling thread until its request completes. The connections
+//! are driven by an internal tokio runtime with a single worker thread, which
+//! is shared by all blocking clients and created on first use; calls from
+//! several threads run concurrently.
+//!
+//! # Panics
+//! The blocking methods must **not** be called from within an async runtime
+//! (such as in an `async fn` run by `#[tokio::main]`), as they would panic.
+//! Use the async clients there instead.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use rust_wistia::blocking::DataClient;
+//!
+//! let client = DataClient::from_env()?;
+//! let media = client.get_media("abc1234567")?;
+//!
+//! println!("{}", media.name);
+//! # Ok::<(), rust_wistia::RustWistiaError>(())
+//! ```
+//!
+mod client;
+#[cfg(feature = "upload-file")]
+mod file;
+#[cfg(feature = "upload-file")]
+mod file_stream;
+mod link;
+
+pub use client::*;
+#[cfg(feature = "upload-file")]
+pub use file::*;
+#[cfg(feature = "upload-file")]
+pub use file_stream::*;
+pub use link::*;
+
+use std::future::Future;
+use std::sync::OnceLock;
+
+use tokio::runtime::{Builder, Runtime};
+
+/// Run a future to completion on the calling thread, within the runtime
+/// shared by the blocking clients.
+///
+/// The runtime is multi-threaded (with one worker), so that its I/O driver
+/// and timers run on the worker thread while each caller polls its own
+/// future; with a current-thread runtime, they would only make progress
+/// while one of the callers held the runtime's core.
+pub(crate) fn block_on<F: Future>(fut: F) -> F::Output {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+    RUNTIME
+        .get_or_init(|| {
+            Builder::new_multi_thread()
+                .worker_threads(1)
+                .thread_name("rust-wistia-blocking")
+                .enable_all()
+                .build()
+                .expect("failed to build the tokio runtime")
+        })
+        .block_on(fut)
+}
@@ -1,10 +1,23 @@
 //! Library-specific constants
 
+/// API endpoint for the Wistia [Data API]
+///
+/// [Data API]: https://wistia.com/support/developers/data-api
+pub const DATA_API: &str = "https://api.wistia.com/v1";
+
 /// API endpoint for the Wistia [Upload API]
 ///
 /// [Upload API]: https://wistia.com/support/developers/upload-api
 pub const UPLOAD_API: &str = "https://upload.wistia.com";
 
+/// Environment variable which can be used to override the base URL of the
+/// Wistia Data API (default: [`DATA_API`]), when `from_env()` is invoked.
+pub const DATA_API_ENV_VAR_NAME: &str = "WISTIA_API_URL";
+
+/// Environment variable which can be used to override the base URL of the
+/// Wistia Upload API (default: [`UPLOAD_API`]), when `from_env()` is invoked.
+pub const UPLOAD_API_ENV_VAR_NAME: &str = "WISTIA_UPLOAD_URL";
+
 /// Environment variable to be used to retrieve the [API access token],
 /// when `WistiaClient::from_env()` is invoked.
 ///
@@ -12,6 +25,9 @@ pub const UPLOAD_API: &str = "https://upload.wistia.com";
 ///
 pub const ENV_VAR_NAME: &str = "WISTIA_API_TOKEN";
 
+/// Default `User-Agent` header sent with each request.
+pub const DEFAULT_USER_AGENT: &str = concat!("rust-wistia/", env!("CARGO_PKG_VERSION"));
+
 /// Default file name when uploading a file stream (via the `StreamUploader`)
 /// via the Upload API.
 pub const DEFAULT_FILENAME: &str = "file.mp4";
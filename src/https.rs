@@ -11,74 +11,57 @@
 //!     * `rust-tls`: enables the rust implementation of TLS (default)
 //!     * `native-tls`: enables the native implementation of TLS using OpenSSL
 //!
+//! The HTTP version, along with timeouts, connection pool settings, any
+//! [`Proxy`] and custom root or client certificates, are configured at
+//! runtime via a [`ClientBuilder`].
+//!
+//! Note that the connectors only permit plain HTTP to `localhost`, so that
+//! the clients can be pointed at a local mock server; any other `http://`
+//! request is refused before it is sent.
+//!
 //! [AWS Lambda]: https://docs.aws.amazon.com/sdk-for-rust/latest/dg/lambda.html
 //!
-use hyper::client::HttpConnector;
-use hyper::Client;
+#[cfg(doc)]
+use crate::api::Proxy;
+use crate::api::{ClientBuilder, ProxyConnector};
+
+use hyper::{Body, Client};
 
 #[cfg(feature = "rust-tls")]
 pub use hyper_rustls as tls;
 #[cfg(not(feature = "rust-tls"))]
 pub use hyper_tls as tls;
-#[cfg(feature = "rust-tls")]
-use rustls::ClientConfig;
-#[cfg(feature = "rust-tls")]
-use tls::ConfigBuilderExt;
-
-#[cfg(all(feature = "rust-tls", feature = "http2"))]
-pub fn get_https_client<T>() -> Client<tls::HttpsConnector<HttpConnector>, T>
-where
-    T: hyper::body::HttpBody + std::marker::Send,
-    <T as hyper::body::HttpBody>::Data: Send,
-{
-    // Prepare the HTTPS connector
-    let https_connector = tls::HttpsConnectorBuilder::new()
-        // .with_native_roots()
-        .with_tls_config(
-            ClientConfig::builder()
-                .with_safe_defaults()
-                .with_native_roots()
-                .with_no_client_auth(),
-        )
-        .https_only()
-        .enable_http2()
-        .build();
 
-    let mut builder = Client::builder();
-    builder.http2_only(true);
-
-    builder.build::<_, T>(https_connector)
-}
+/// The `rustls` crate, to build a configuration for
+/// [`ClientBuilder::rustls_config`].
+#[cfg(feature = "rust-tls")]
+pub use rustls;
 
-#[cfg(all(feature = "rust-tls", not(feature = "http2")))]
-pub fn get_https_client<T>() -> Client<tls::HttpsConnector<HttpConnector>, T>
-where
-    T: hyper::body::HttpBody + std::marker::Send,
-    <T as hyper::body::HttpBody>::Data: Send,
-{
-    // Prepare the HTTPS connector
-    let https_connector = tls::HttpsConnectorBuilder::new()
-        // .with_native_roots()
-        .with_tls_config(
-            ClientConfig::builder()
-                .with_safe_defaults()
-                .with_native_roots()
-                .with_no_client_auth(),
-        )
-        .https_only()
-        .enable_http2()
-        .build();
+/// The `native-tls` crate, to build a connector for
+/// `ClientBuilder::native_tls_connector`.
+#[cfg(not(feature = "rust-tls"))]
+pub use native_tls;
 
-    Client::builder().build::<_, T>(https_connector)
-}
+/// The HTTPS client which sends requests, and connects through a proxy if
+/// one is configured.
+pub type HttpsClient<B = Body> = Client<tls::HttpsConnector<ProxyConnector>, B>;
 
-#[cfg(not(feature = "rust-tls"))]
-pub fn get_https_client<T>() -> Client<tls::HttpsConnector<HttpConnector>, T>
+/// Returns an HTTPS client with the default settings, which uses any proxy
+/// set in the environment.
+///
+/// Use a [`ClientBuilder`] to configure the client, such as to set a
+/// connect timeout or to select the HTTP version at runtime.
+///
+/// # Panics
+/// If the TLS configuration cannot be built, such as when the native root
+/// certificates cannot be loaded; [`ClientBuilder::https_client`] returns
+/// an error instead.
+pub fn get_https_client<T>() -> HttpsClient<T>
 where
     T: hyper::body::HttpBody + std::marker::Send,
     <T as hyper::body::HttpBody>::Data: Send,
 {
-    // Prepare the HTTPS connector
-    let https_connector = tls::HttpsConnector::new();
-    Client::builder().build::<_, T>(https_connector)
+    ClientBuilder::new()
+        .https_client()
+        .expect("failed to build the HTTPS client")
 }
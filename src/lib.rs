@@ -62,30 +62,33 @@
 
 mod api;
 pub mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod constants;
 pub mod https;
+pub mod mock;
 pub mod models;
 pub mod status;
 pub mod types;
 pub mod utils;
 
 pub use api::*;
+pub use auth::{AccessToken, EnvToken, FileToken, FnToken, TokenFuture, TokenProvider};
 pub use https::tls;
 pub use models::error::*;
 pub use types::*;
 
 #[cfg(feature = "logging")]
 mod log {
-    pub use log::{debug, error, trace, warn};
+    pub use log::{debug, error, warn};
 }
 
 #[cfg(not(feature = "logging"))]
 mod log {
     macro_rules! debug      ( ($($tt:tt)*) => {{}} );
     macro_rules! error      ( ($($tt:tt)*) => {{}} );
-    macro_rules! trace      ( ($($tt:tt)*) => {{}} );
     macro_rules! warning    ( ($($tt:tt)*) => {{}} );
-    pub(crate) use {debug, error, trace, warning as warn};
+    pub(crate) use {debug, error, warning as warn};
 }
 
 #[cfg(test)]
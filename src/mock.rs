@@ -0,0 +1,393 @@
+//! A mock [`Transport`], to test code which uses the Wistia API clients
+//! without a network.
+//!
+//! A [`MockTransport`] matches each request against the [`Mock`]s it is
+//! given, by method, path and query parameters, and returns the scripted
+//! response of the first match. Every request is recorded as a [`Call`], so
+//! that tests can assert exactly which calls were made.
+//!
+//! # Examples
+//!
+//! ```
+//! use rust_wistia::mock::{Mock, MockTransport};
+//! use rust_wistia::DataClient;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> rust_wistia::Result<()> {
+//! let transport = MockTransport::new();
+//! transport.mock(Mock::get("/v1/medias.json").query("project_id", "xyz").body("[]"));
+//!
+//! let client = DataClient::with_transport("my-token", transport.clone());
+//! let url = client.api_url("medias.json?project_id=xyz");
+//! let medias: Vec<rust_wistia::models::Media> = client.get(&url).await?;
+//!
+//! assert!(medias.is_empty());
+//! transport.assert_called_times("GET", "/v1/medias.json", 1);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Transport`]: crate::Transport
+//!
+use crate::api::{BoxError, ResponseFuture};
+use crate::types::Result;
+use crate::RustWistiaError;
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use hyper::body::Bytes;
+use hyper::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use serde::Serialize;
+use tower_service::Service;
+
+/// A scripted response, for requests which match a method, a path and
+/// (optionally) some query parameters.
+#[derive(Debug, Clone)]
+pub struct Mock {
+    method: Method,
+    path: String,
+    query: Vec<(String, String)>,
+    status: StatusCode,
+    headers: Vec<(String, String)>,
+    body: Bytes,
+    times: Option<usize>,
+}
+
+impl Mock {
+    /// A mock for requests with the `method` to the `path`, such as
+    /// `/v1/medias.json`, which responds with `200 OK` and an empty body.
+    ///
+    /// # Panics
+    /// If `method` is not a valid HTTP method.
+    pub fn new(method: &str, path: &str) -> Self {
+        Self {
+            method: Method::from_bytes(method.as_bytes()).expect("invalid HTTP method"),
+            path: path.to_owned(),
+            query: Vec::new(),
+            status: StatusCode::OK,
+            headers: Vec::new(),
+            body: Bytes::new(),
+            times: None,
+        }
+    }
+
+    /// A mock for `GET` requests to the `path`.
+    pub fn get(path: &str) -> Self {
+        Self::new("GET", path)
+    }
+
+    /// A mock for `POST` requests to the `path`.
+    pub fn post(path: &str) -> Self {
+        Self::new("POST", path)
+    }
+
+    /// A mock for `PUT` requests to the `path`.
+    pub fn put(path: &str) -> Self {
+        Self::new("PUT", path)
+    }
+
+    /// A mock for `PATCH` requests to the `path`.
+    pub fn patch(path: &str) -> Self {
+        Self::new("PATCH", path)
+    }
+
+    /// A mock for `DELETE` requests to the `path`.
+    pub fn delete(path: &str) -> Self {
+        Self::new("DELETE", path)
+    }
+
+    /// Only match requests with the query parameter `name` set to `value`.
+    pub fn query(mut self, name: &str, value: &str) -> Self {
+        self.query.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Sets the status code of the response (default: `200`).
+    ///
+    /// # Panics
+    /// If `status` is not a valid status code.
+    pub fn status(mut self, status: u16) -> Self {
+        self.status = StatusCode::from_u16(status).expect("invalid status code");
+        self
+    }
+
+    /// Adds a header to the response.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Sets the body of the response.
+    pub fn body(mut self, body: impl Into<Bytes>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Sets the body of the response to a value serialized as JSON.
+    ///
+    /// # Panics
+    /// If the value cannot be serialized.
+    pub fn json<T: Serialize>(self, value: &T) -> Self {
+        let body = serde_json::to_vec(value).expect("failed to serialize JSON");
+        self.header(CONTENT_TYPE.as_str(), "application/json")
+            .body(body)
+    }
+
+    /// Only respond to the first `n` matching requests; after that, later
+    /// mocks for the same request are used instead (default: no limit).
+    pub fn times(mut self, n: usize) -> Self {
+        self.times = Some(n);
+        self
+    }
+
+    fn matches(&self, call: &Call) -> bool {
+        self.times != Some(0)
+            && self.method == call.method
+            && self.path == call.path
+            && self.query.iter().all(|(name, value)| {
+                call.query_pairs()
+                    .iter()
+                    .any(|(n, v)| n == name && v == value)
+            })
+    }
+
+    fn response(&self) -> Result<Response<Body>> {
+        let mut builder = Response::builder().status(self.status);
+
+        for (name, value) in &self.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+
+        Ok(builder.body(Body::from(self.body.clone()))?)
+    }
+}
+
+/// A request which was sent to a [`MockTransport`].
+#[derive(Debug, Clone)]
+pub struct Call {
+    /// The request method.
+    pub method: Method,
+    /// The path of the request URL, such as `/v1/medias.json`.
+    pub path: String,
+    /// The raw query string of the request URL, if any.
+    pub query: Option<String>,
+    /// The request headers.
+    pub headers: HeaderMap,
+    /// The full request body.
+    pub body: Bytes,
+}
+
+impl Call {
+    /// Returns the (decoded) value of a query parameter.
+    pub fn query_param(&self, name: &str) -> Option<String> {
+        self.query_pairs()
+            .into_iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v)
+    }
+
+    /// Returns the value of a request header.
+    pub fn header(&self, name: &str) -> Option<&HeaderValue> {
+        let name = HeaderName::from_bytes(name.as_bytes()).ok()?;
+        self.headers.get(name)
+    }
+
+    fn query_pairs(&self) -> Vec<(String, String)> {
+        self.query
+            .as_deref()
+            .and_then(|query| serde_urlencoded::from_str(query).ok())
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.path)?;
+        if let Some(query) = &self.query {
+            write!(f, "?{query}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    mocks: Vec<Mock>,
+    calls: Vec<Call>,
+}
+
+/// A [`Transport`] which returns scripted responses, and records each
+/// request so that tests can assert which calls were made.
+///
+/// Clones of a `MockTransport` share the same mocks and calls, so keep a
+/// clone to make assertions after it is passed to a client. A request which
+/// does not match any mock fails with a [`RustWistiaError::Transport`].
+///
+/// [`Transport`]: crate::Transport
+#[derive(Debug, Clone, Default)]
+pub struct MockTransport {
+    state: Arc<Mutex<State>>,
+}
+
+impl MockTransport {
+    /// Create a new `MockTransport`, with no mocks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a mock; mocks are matched in the order they are added.
+    pub fn mock(&self, mock: Mock) -> &Self {
+        self.state.lock().unwrap().mocks.push(mock);
+        self
+    }
+
+    /// Returns the requests which were sent, in order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.state.lock().unwrap().calls.clone()
+    }
+
+    /// Returns the requests which were sent with the `method` to the `path`.
+    pub fn calls_to(&self, method: &str, path: &str) -> Vec<Call> {
+        self.calls()
+            .into_iter()
+            .filter(|call| call.method.as_str() == method && call.path == path)
+            .collect()
+    }
+
+    /// Asserts that a request was sent with the `method` to the `path`.
+    ///
+    /// # Panics
+    /// If no such request was sent.
+    #[track_caller]
+    pub fn assert_called(&self, method: &str, path: &str) {
+        if self.calls_to(method, path).is_empty() {
+            panic!(
+                "expected a call to {method} {path}, but got: {:?}",
+                self.call_list()
+            );
+        }
+    }
+
+    /// Asserts that exactly `n` requests were sent with the `method` to the
+    /// `path`.
+    ///
+    /// # Panics
+    /// If a different number of such requests were sent.
+    #[track_caller]
+    pub fn assert_called_times(&self, method: &str, path: &str, n: usize) {
+        let count = self.calls_to(method, path).len();
+        if count != n {
+            panic!(
+                "expected {n} call(s) to {method} {path}, but got {count}: {:?}",
+                self.call_list()
+            );
+        }
+    }
+
+    /// Asserts that no requests were sent.
+    ///
+    /// # Panics
+    /// If any request was sent.
+    #[track_caller]
+    pub fn assert_no_calls(&self) {
+        let calls = self.call_list();
+        if !calls.is_empty() {
+            panic!("expected no calls, but got: {calls:?}");
+        }
+    }
+
+    fn call_list(&self) -> Vec<String> {
+        self.calls().iter().map(Call::to_string).collect()
+    }
+
+    /// Record a call, and return the response of the first matching mock.
+    fn respond(&self, call: Call) -> Result<Response<Body>> {
+        let mut state = self.state.lock().unwrap();
+
+        let resp = match state.mocks.iter_mut().find(|mock| mock.matches(&call)) {
+            Some(mock) => {
+                if let Some(times) = &mut mock.times {
+                    *times -= 1;
+                }
+                mock.response()
+            }
+            None => {
+                let err: BoxError = format!("no mock matches the request: {call}").into();
+                Err(RustWistiaError::Transport(err))
+            }
+        };
+
+        state.calls.push(call);
+
+        resp
+    }
+}
+
+impl Service<Request<Body>> for MockTransport {
+    type Response = Response<Body>;
+    type Error = RustWistiaError;
+    type Future = ResponseFuture;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let transport = self.clone();
+
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+            let body = hyper::body::to_bytes(body).await?;
+
+            transport.respond(Call {
+                method: parts.method,
+                path: parts.uri.path().to_owned(),
+                query: parts.uri.query().map(str::to_owned),
+                headers: parts.headers,
+                body,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(method: Method, uri: &str) -> Call {
+        let uri: hyper::Uri = uri.parse().unwrap();
+
+        Call {
+            method,
+            path: uri.path().to_owned(),
+            query: uri.query().map(str::to_owned),
+            headers: HeaderMap::new(),
+            body: Bytes::new(),
+        }
+    }
+
+    #[test]
+    fn test_mock_matches() {
+        let mock = Mock::get("/v1/medias.json").query("name", "My Video");
+
+        assert!(mock.matches(&call(
+            Method::GET,
+            "https://api.wistia.com/v1/medias.json?page=1&name=My+Video"
+        )));
+        assert!(!mock.matches(&call(
+            Method::GET,
+            "https://api.wistia.com/v1/medias.json?page=1"
+        )));
+        assert!(!mock.matches(&call(
+            Method::POST,
+            "https://api.wistia.com/v1/medias.json?name=My+Video"
+        )));
+        assert!(!mock.clone().times(0).matches(&call(
+            Method::GET,
+            "https://api.wistia.com/v1/medias.json?name=My+Video"
+        )));
+    }
+}
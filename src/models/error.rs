@@ -1,10 +1,20 @@
 //! Library-specific errors, including types and implementations.
 //!
+use crate::api::{is_retryable_status, BoxError};
+
+use hyper::body::Bytes;
+use hyper::header::HeaderMap;
 use hyper::http::uri::InvalidUri;
+use hyper::Method;
 
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::io;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use thiserror::Error;
 
 /// Base error types raised by this library.
@@ -30,6 +40,33 @@ pub enum RustWistiaError {
     /// A `media_id` or `media` argument is not specified
     #[error("An argument for `media_id` or `media` is required.")]
     MediaIsRequired,
+    /// The input is not a recognized Wistia URL or embed code
+    #[error("unrecognized Wistia URL or embed code: {input:?}")]
+    UnrecognizedEmbed { input: String },
+    /// An ID is not in the expected format
+    #[error("invalid {kind}: {id:?}")]
+    InvalidId { kind: &'static str, id: String },
+    /// A base URL for the Wistia API is not valid
+    #[error("invalid base url `{url}`: {reason}")]
+    InvalidBaseUrl { url: String, reason: &'static str },
+    /// A request URL uses plain HTTP for a host other than `localhost`
+    #[error("insecure url `{url}`: plain HTTP is only allowed for localhost")]
+    InsecureUrl { url: String },
+    /// A proxy URL is not valid
+    #[error("invalid proxy url `{url}`: {reason}")]
+    InvalidProxy { url: String, reason: &'static str },
+    /// There is no Wistia account for a tenant of a `ClientRegistry`
+    #[error("no Wistia account for tenant `{tenant}`")]
+    UnknownTenant { tenant: String },
+    /// A TLS certificate, key or configuration is not valid
+    #[error("invalid TLS configuration: {0}")]
+    Tls(String),
+    /// A request did not complete within the configured timeout
+    #[error("request timed out after {0:?}")]
+    Timeout(Duration),
+    /// An error raised by a middleware layer around the HTTP transport
+    #[error("transport error: {0}")]
+    Transport(#[source] BoxError),
     /// `RequestError` is raised when the Wistia API responds back with a
     /// *non-* "OK" response.
     ///
@@ -37,6 +74,9 @@ pub enum RustWistiaError {
     /// a response is between 400 and 600, which indicates its either a client
     /// error or a server error.
     ///
+    /// Use [`RustWistiaError::kind`] to classify the error, such as when a
+    /// media is not found or the request is rate limited.
+    ///
     /// # Note
     ///
     /// The `error` and `message` fields are mutually-exclusive; if we cannot
@@ -49,7 +89,13 @@ pub enum RustWistiaError {
     Request {
         status_code: u16,
         reason: String,
-        error: WistiaError,
+        error: Box<WistiaError>,
+        /// How long to wait before sending the request again, as set by
+        /// the `Retry-After` header of the response.
+        retry_after: Option<Duration>,
+        /// The method and URL of the request, along with the headers and
+        /// raw body of the response.
+        context: Box<RequestContext>,
     },
     #[error("unknown rust-wistia error")]
     Unknown,
@@ -68,14 +114,411 @@ pub enum RustWistiaError {
     SerdeUrlEncodedSer(#[from] serde_urlencoded::ser::Error),
 }
 
+/// The kind of a [`RustWistiaError`], so that callers can branch on an
+/// error without matching on the `reason` of a failed request.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use rust_wistia::{DataClient, ErrorKind};
+///
+/// # async fn example(client: DataClient) -> rust_wistia::Result<()> {
+/// match client.get_media("abc1234567").await {
+///     Ok(media) => println!("{}", media.name),
+///     Err(e) if e.kind() == ErrorKind::NotFound => println!("no such media"),
+///     Err(e) => return Err(e),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The resource, such as a media, does not exist (a `404`).
+    NotFound,
+    /// The access token is missing, invalid or expired (a `401`).
+    Unauthorized,
+    /// The access token does not have permission for the request (a `403`).
+    Forbidden,
+    /// Too many requests were sent (a `429`); `retry_after` is how long to
+    /// wait before sending the request again, if the response said so.
+    RateLimited { retry_after: Option<Duration> },
+    /// The request is not valid, such as when a parameter is missing or
+    /// invalid (any other `4xx`), or it could not be built.
+    Validation,
+    /// The Wistia API failed to handle the request (a `5xx`).
+    Server,
+    /// The request did not complete within a timeout.
+    Timeout,
+    /// Any other error, such as a connection or I/O error.
+    Other,
+}
+
+impl RustWistiaError {
+    /// Returns the kind of the error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Request {
+                status_code,
+                retry_after,
+                ..
+            } => match status_code {
+                401 => ErrorKind::Unauthorized,
+                403 => ErrorKind::Forbidden,
+                404 => ErrorKind::NotFound,
+                429 => ErrorKind::RateLimited {
+                    retry_after: *retry_after,
+                },
+                500..=599 => ErrorKind::Server,
+                _ => ErrorKind::Validation,
+            },
+            Self::Timeout(_) => ErrorKind::Timeout,
+            Self::Hyper(e) if e.is_timeout() => ErrorKind::Timeout,
+            Self::MediaIsRequired
+            | Self::InvalidId { .. }
+            | Self::InvalidBaseUrl { .. }
+            | Self::UnrecognizedEmbed { .. }
+            | Self::InvalidUri(_)
+            | Self::SerdeUrlEncodedSer(_) => ErrorKind::Validation,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    /// Returns the status code of the response, if the Wistia API responded
+    /// with an error.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Request { status_code, .. } => Some(*status_code),
+            _ => None,
+        }
+    }
+
+    /// Returns the request and response, if the Wistia API responded with
+    /// an error.
+    pub fn context(&self) -> Option<&RequestContext> {
+        match self {
+            Self::Request { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Returns the ID of the request, if the Wistia API responded with an
+    /// error which includes it; see [`RequestContext::request_id`].
+    pub fn request_id(&self) -> Option<&str> {
+        self.context()?.request_id()
+    }
+
+    /// Checks if the request might succeed if it is sent again, such as
+    /// after it is rate limited, a server error, a timeout or a connection
+    /// error.
+    ///
+    /// The same errors are retried by the [`RetryService`](crate::RetryService)
+    /// of a client, for requests which can be retried; a request which is
+    /// not idempotent is only retried on a connection error.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Request { status_code, .. } => is_retryable_status(*status_code),
+            Self::Timeout(_) => true,
+            Self::Hyper(e) => {
+                e.is_connect() || e.is_closed() || e.is_incomplete_message() || e.is_timeout()
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The request and response of a [`RustWistiaError::Request`], which can
+/// help to debug a failed request, or to raise a ticket with Wistia support.
+///
+/// The `Debug` output only includes the start of the body, which can be
+/// large; use [`RequestContext::body_text`] for all of it.
+#[derive(Clone)]
+pub struct RequestContext {
+    /// The method of the request, if known; it is not set by
+    /// [`raise_for_status`](crate::status::raise_for_status).
+    pub method: Option<Method>,
+    /// The URL of the request, with any access token redacted.
+    pub url: String,
+    /// The headers of the response.
+    pub headers: HeaderMap,
+    /// The raw body of the response, with any access token redacted.
+    pub body: Bytes,
+}
+
+impl RequestContext {
+    /// Returns the ID which the server assigned to the request, from the
+    /// `X-Request-Id` header of the response.
+    pub fn request_id(&self) -> Option<&str> {
+        self.headers.get("x-request-id")?.to_str().ok()
+    }
+
+    /// Returns the body of the response as text, replacing any invalid
+    /// UTF-8 sequences.
+    pub fn body_text(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.body)
+    }
+}
+
+impl fmt::Debug for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// The most characters of the body to include.
+        const MAX_BODY_CHARS: usize = 256;
+
+        let body = self.body_text();
+        let body = match body.char_indices().nth(MAX_BODY_CHARS) {
+            Some((end, _)) => format!("{}... ({} bytes)", &body[..end], self.body.len()),
+            None => body.into_owned(),
+        };
+
+        f.debug_struct("RequestContext")
+            .field("method", &self.method)
+            .field("url", &self.url)
+            .field("headers", &self.headers)
+            .field("body", &body)
+            .finish()
+    }
+}
+
 /// An error returned from the Wistia API, along with a custom error
 /// code from the Wistia side.
 ///
+/// Besides the usual `{"error": "..."}`, the other shapes of error which
+/// Wistia responds with are parsed as well: a list of messages such as
+/// `{"errors": ["..."]}`, and errors for each field such as
+/// `{"errors": {"name": ["can't be blank"]}}`. If there is no `error`
+/// message, the `message` is then joined from these.
+///
 #[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(from = "Value")]
 pub struct WistiaError {
-    #[serde(default)]
     #[serde(rename = "error")]
     pub message: String,
     pub code: Option<String>,
     pub detail: Option<String>,
+    /// A list of error messages, which are not for a specific field.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub messages: Vec<String>,
+    /// The error messages for each (invalid) field of the request.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub field_errors: BTreeMap<String, Vec<String>>,
+}
+
+impl From<Value> for WistiaError {
+    fn from(value: Value) -> Self {
+        let mut error = Self::default();
+        error.add(value);
+
+        if error.message.is_empty() {
+            let fields = error.field_errors.iter().flat_map(|(field, messages)| {
+                messages
+                    .iter()
+                    .map(move |message| format!("{field}: {message}"))
+            });
+            error.message = error
+                .messages
+                .iter()
+                .cloned()
+                .chain(fields)
+                .collect::<Vec<_>>()
+                .join("; ");
+        }
+
+        error
+    }
+}
+
+impl WistiaError {
+    /// Add the error message(s) in a JSON `value`.
+    fn add(&mut self, value: Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    match (key.as_str(), value) {
+                        ("error" | "message", Value::String(message))
+                            if self.message.is_empty() =>
+                        {
+                            self.message = message;
+                        }
+                        ("error", value @ Value::Object(_)) => self.add(value),
+                        ("code", value) => self.code = json_to_string(value),
+                        ("detail", value) => self.detail = json_to_string(value),
+                        ("errors", Value::Object(fields)) => {
+                            for (field, value) in fields {
+                                self.add_field_error(field, value);
+                            }
+                        }
+                        ("errors", value @ Value::Array(_)) => self.add(value),
+                        _ => {}
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    match item {
+                        Value::Object(mut map) if map.contains_key("field") => {
+                            let field = map.remove("field").and_then(json_to_string);
+                            let message = map.remove("message").unwrap_or_default();
+                            self.add_field_error(field.unwrap_or_default(), message);
+                        }
+                        Value::Object(map) => {
+                            let mut error = Self::default();
+                            error.add(Value::Object(map));
+                            if !error.message.is_empty() {
+                                self.messages.push(error.message);
+                            }
+                        }
+                        item => self.messages.extend(json_to_string(item)),
+                    }
+                }
+            }
+            value => self.message = json_to_string(value).unwrap_or_default(),
+        }
+    }
+
+    /// Add the error message(s) in a JSON `value` for a `field`.
+    fn add_field_error(&mut self, field: String, value: Value) {
+        let messages = self.field_errors.entry(field).or_default();
+
+        match value {
+            Value::Array(items) => messages.extend(items.into_iter().filter_map(json_to_string)),
+            value => messages.extend(json_to_string(value)),
+        }
+    }
+}
+
+/// Returns a JSON `value` as a string, unless it is `null`.
+fn json_to_string(value: Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s),
+        value => Some(value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_error(status_code: u16, retry_after: Option<Duration>) -> RustWistiaError {
+        RustWistiaError::Request {
+            status_code,
+            reason: String::new(),
+            error: Box::default(),
+            retry_after,
+            context: Box::new(RequestContext {
+                method: Some(Method::GET),
+                url: "https://api.wistia.com/v1/medias.json".to_owned(),
+                headers: HeaderMap::new(),
+                body: Bytes::new(),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_kind() {
+        let cases = [
+            (400, ErrorKind::Validation),
+            (401, ErrorKind::Unauthorized),
+            (403, ErrorKind::Forbidden),
+            (404, ErrorKind::NotFound),
+            (422, ErrorKind::Validation),
+            (500, ErrorKind::Server),
+            (503, ErrorKind::Server),
+        ];
+        for (status_code, kind) in cases {
+            let err = request_error(status_code, None);
+            assert_eq!(err.kind(), kind, "{status_code}");
+            assert_eq!(err.status(), Some(status_code));
+        }
+
+        let err = request_error(429, Some(Duration::from_secs(3)));
+        assert_eq!(
+            err.kind(),
+            ErrorKind::RateLimited {
+                retry_after: Some(Duration::from_secs(3))
+            }
+        );
+
+        let err = RustWistiaError::Timeout(Duration::from_secs(1));
+        assert_eq!(err.kind(), ErrorKind::Timeout);
+        assert_eq!(err.status(), None);
+        assert_eq!(
+            RustWistiaError::MediaIsRequired.kind(),
+            ErrorKind::Validation
+        );
+        assert_eq!(RustWistiaError::Unknown.kind(), ErrorKind::Other);
+    }
+
+    #[test]
+    fn test_wistia_error() {
+        let parse = |json: &str| serde_json::from_str::<WistiaError>(json).unwrap();
+
+        let error = parse(r#"{"error": "Media not found", "code": "not_found"}"#);
+        assert_eq!(error.message, "Media not found");
+        assert_eq!(error.code.as_deref(), Some("not_found"));
+
+        let error = parse(r#"{"errors": ["Invalid token", "Expired"]}"#);
+        assert_eq!(error.message, "Invalid token; Expired");
+        assert_eq!(error.messages, ["Invalid token", "Expired"]);
+
+        let error = parse(r#"{"errors": {"name": ["can't be blank"], "project_id": "invalid"}}"#);
+        assert_eq!(error.message, "name: can't be blank; project_id: invalid");
+        assert_eq!(error.field_errors["name"], ["can't be blank"]);
+        assert_eq!(error.field_errors["project_id"], ["invalid"]);
+
+        let error = parse(r#"[{"field": "name", "message": "is too long"}, "Oops"]"#);
+        assert_eq!(error.messages, ["Oops"]);
+        assert_eq!(error.field_errors["name"], ["is too long"]);
+
+        let error = parse(r#"{"error": {"message": "Bad request", "code": 400}}"#);
+        assert_eq!(error.message, "Bad request");
+        assert_eq!(error.code.as_deref(), Some("400"));
+
+        // The error is serialized in the usual shape
+        let json = serde_json::to_value(parse(r#"{"error": "Oops"}"#)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"error": "Oops", "code": null, "detail": null})
+        );
+    }
+
+    #[test]
+    fn test_request_id() {
+        let mut err = request_error(500, None);
+        assert_eq!(err.request_id(), None);
+
+        if let RustWistiaError::Request { context, .. } = &mut err {
+            context
+                .headers
+                .insert("x-request-id", "abc-123".parse().unwrap());
+        }
+        assert_eq!(err.request_id(), Some("abc-123"));
+        assert_eq!(err.context().unwrap().method, Some(Method::GET));
+    }
+
+    #[test]
+    fn test_context_debug_truncates_body() {
+        let mut context = RequestContext {
+            method: Some(Method::GET),
+            url: "https://api.wistia.com/v1/medias.json".to_owned(),
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(b"{\"error\": \"not found\"}"),
+        };
+        assert!(format!("{context:?}").contains(r#"{\"error\": \"not found\"}"#));
+
+        context.body = Bytes::from("x".repeat(10_000));
+        let debug = format!("{context:?}");
+        assert!(debug.contains("... (10000 bytes)"));
+        assert!(debug.len() < 1_000);
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(request_error(429, None).is_retryable());
+        assert!(request_error(503, None).is_retryable());
+        assert!(!request_error(404, None).is_retryable());
+        assert!(!request_error(501, None).is_retryable());
+        assert!(RustWistiaError::Timeout(Duration::from_secs(1)).is_retryable());
+        assert!(!RustWistiaError::MediaIsRequired.is_retryable());
+    }
 }
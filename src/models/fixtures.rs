@@ -0,0 +1,85 @@
+//! JSON responses shared by the tests of the models.
+//!
+use serde_json::Value;
+
+/// A media, as returned by the [Medias#Show] endpoint of the Data API.
+///
+/// [Medias#Show]: https://wistia.com/support/developers/data-api#medias-show
+pub(crate) const MEDIA_JSON: &str = r#"
+{
+  "hashed_id": "v80gyfkt28",
+  "id": 4489021,
+  "name": "How They Work: Zappos",
+  "type": "Video",
+  "created": "2013-09-19T15:30:49+00:00",
+  "updated": "2013-10-28T20:53:16+00:00",
+  "duration": 167.0,
+  "status": "ready",
+  "description": "",
+  "progress": 1.0,
+  "thumbnail": {
+    "url": "http://embed.wistia.com/deliveries/7fbf9c2fe9c6585f9aa032f43f0aecc3f287e86b.jpg?image_crop_resized=100x60",
+    "width": 100,
+    "height": 60
+  },
+  "project": {
+    "hashed_id": "4d23503f70",
+    "id": 464427,
+    "name": "How They Work"
+  },
+  "embedCode": "",
+  "assets": [
+    {
+      "url": "http://embed.wistia.com/deliveries/856970d9a4b5d9d2a8d5c4b1d3d7a8f0.bin",
+      "width": 960,
+      "height": 540,
+      "fileSize": 23695556,
+      "contentType": "video/mp4",
+      "type": "OriginalFile"
+    }
+  ],
+  "section": null
+}
+"#;
+
+/// The [example response] of the Upload API.
+///
+/// [example response]: https://wistia.com/support/developers/upload-api#example-response
+pub(crate) const UPLOAD_JSON: &str = r#"
+{
+  "id": 2208087,
+  "account_id": 123456789,
+  "name": "dramatic_squirrel.mp4",
+  "description": "",
+  "type": "Video",
+  "archived": false,
+  "created": "2012-10-26T16:47:09+00:00",
+  "updated": "2012-10-26T16:47:10+00:00",
+  "duration": 5.333000183105469,
+  "hashed_id": "gn69c10tqw",
+  "progress": 0.0,
+  "thumbnail":
+  {
+    "url": "http://embed.wistia.com/deliveries/ffbada01610466e66f67a5dbbf473ed6574a6405.jpg?image_crop_resized=100x60",
+    "width": 100,
+    "height": 60
+  }
+}
+"#;
+
+/// Parse a `json` response, replacing its top-level `fields`; a field
+/// which is `null` is removed instead.
+pub(crate) fn with_fields(json: &str, fields: Value) -> Value {
+    let mut value: Value = serde_json::from_str(json).unwrap();
+    let object = value.as_object_mut().unwrap();
+
+    for (key, field) in fields.as_object().unwrap() {
+        if field.is_null() {
+            object.remove(key);
+        } else {
+            object.insert(key.clone(), field.clone());
+        }
+    }
+
+    value
+}
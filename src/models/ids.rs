@@ -0,0 +1,306 @@
+//! Typed identifiers for Wistia resources, so that (for example) a project
+//! ID can't be passed where a media hashed ID is expected.
+//!
+//! A hashed ID is validated by `new()`, [`str::parse`] and `TryFrom<String>`,
+//! and when it is de-serialized. The `From<&str>` conversion is kept for
+//! compatibility and performs **no** validation, as does `new_unchecked()`;
+//! for a [`MediaHashedId`], it first tries to parse a Wistia URL or embed
+//! code via [`EmbedInfo::parse`].
+//! An unvalidated ID is percent-encoded when it is added to the path of a
+//! request URL, so it can't point the request at another endpoint.
+//!
+use crate::models::EmbedInfo;
+use crate::{Result, RustWistiaError};
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Length of a hashed ID, such as `abc1234567`.
+const HASHED_ID_LEN: usize = 10;
+
+macro_rules! hashed_id {
+    ($(#[$meta:meta])* $name:ident, $kind:literal) => {
+        $(#[$meta])*
+        #[derive(Default, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(try_from = "String", into = "String")]
+        pub struct $name(String);
+
+        impl $name {
+            /// Create a new ID, validating that it is a 10-character,
+            /// lowercase alphanumeric string.
+            pub fn new(id: impl Into<String>) -> Result<Self> {
+                let id = id.into();
+
+                if id.len() == HASHED_ID_LEN
+                    && id.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+                {
+                    Ok(Self(id))
+                } else {
+                    Err(RustWistiaError::InvalidId { kind: $kind, id })
+                }
+            }
+
+            /// Create a new ID **without** validating its format, such as for
+            /// an ID in a format which is not (yet) known to this library.
+            pub fn new_unchecked(id: impl Into<String>) -> Self {
+                Self(id.into())
+            }
+
+            /// Returns the ID as a string slice.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Consumes the ID, returning the inner string.
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = RustWistiaError;
+
+            fn from_str(s: &str) -> Result<Self> {
+                Self::new(s)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = RustWistiaError;
+
+            fn try_from(id: String) -> Result<Self> {
+                Self::new(id)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl From<&$name> for $name {
+            fn from(id: &$name) -> Self {
+                id.clone()
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+macro_rules! numeric_id {
+    ($(#[$meta:meta])* $name:ident, $kind:literal) => {
+        $(#[$meta])*
+        #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(u64);
+
+        impl $name {
+            /// Create a new ID from its numeric value.
+            pub const fn new(id: u64) -> Self {
+                Self(id)
+            }
+
+            /// Returns the numeric value of the ID.
+            pub const fn get(self) -> u64 {
+                self.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = RustWistiaError;
+
+            fn from_str(s: &str) -> Result<Self> {
+                s.parse().map(Self).map_err(|_| RustWistiaError::InvalidId {
+                    kind: $kind,
+                    id: s.to_owned(),
+                })
+            }
+        }
+
+        impl From<u64> for $name {
+            fn from(id: u64) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<$name> for u64 {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl PartialEq<u64> for $name {
+            fn eq(&self, other: &u64) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+hashed_id!(
+    /// The hashed ID of a media (example: `abc1234567`).
+    MediaHashedId,
+    "media hashed id"
+);
+
+hashed_id!(
+    /// The hashed ID of a project (example: `xyz1234567`).
+    ProjectHashedId,
+    "project hashed id"
+);
+
+numeric_id!(
+    /// The numeric ID of a media.
+    MediaId,
+    "media id"
+);
+
+numeric_id!(
+    /// The numeric ID of a project.
+    ProjectId,
+    "project id"
+);
+
+numeric_id!(
+    /// The numeric ID of a Wistia account.
+    AccountId,
+    "account id"
+);
+
+impl From<&str> for MediaHashedId {
+    /// Retrieve the hashed ID from a URL or embed code, such as one pasted
+    /// by a user, or else use the input as the ID without validation.
+    fn from(id: &str) -> Self {
+        match EmbedInfo::parse(id) {
+            Ok(info) => info.hashed_id,
+            Err(_) => Self::new_unchecked(id),
+        }
+    }
+}
+
+impl From<&String> for MediaHashedId {
+    /// Retrieve the hashed ID from a URL or embed code, such as one pasted
+    /// by a user, or else use the input as the ID without validation.
+    fn from(id: &String) -> Self {
+        Self::from(id.as_str())
+    }
+}
+
+impl From<&str> for ProjectHashedId {
+    fn from(id: &str) -> Self {
+        Self::new_unchecked(id)
+    }
+}
+
+impl From<&String> for ProjectHashedId {
+    fn from(id: &String) -> Self {
+        Self::new_unchecked(id.as_str())
+    }
+}
+
+impl From<EmbedInfo> for MediaHashedId {
+    /// Retrieve the hashed ID from a parsed URL or embed code
+    fn from(info: EmbedInfo) -> Self {
+        info.hashed_id
+    }
+}
+
+impl From<&EmbedInfo> for MediaHashedId {
+    /// Retrieve the hashed ID from a parsed URL or embed code
+    fn from(info: &EmbedInfo) -> Self {
+        info.hashed_id.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hashed_id_validation() {
+        assert!("abc1234567".parse::<MediaHashedId>().is_ok());
+
+        for id in ["", "abc123", "abc12345678", "ABC1234567", "abc-123456"] {
+            let err = id.parse::<MediaHashedId>().unwrap_err();
+            assert!(matches!(
+                err,
+                RustWistiaError::InvalidId {
+                    kind: "media hashed id",
+                    ..
+                }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_numeric_id_validation() {
+        assert_eq!("2208087".parse::<MediaId>().unwrap(), 2208087);
+        assert!("22o8087".parse::<ProjectId>().is_err());
+    }
+
+    #[test]
+    fn test_lenient_conversions() {
+        let id = MediaHashedId::from("ABC-123");
+        assert_eq!(id, "ABC-123");
+        assert_eq!(MediaHashedId::new_unchecked("ABC-123"), id);
+
+        assert!(MediaHashedId::try_from("ABC-123".to_owned()).is_err());
+    }
+
+    #[test]
+    fn test_media_hashed_id_from_embed() {
+        for input in [
+            "abc1234567",
+            "https://my-account.wistia.com/medias/abc1234567",
+            "https://fast.wistia.net/embed/iframe/abc1234567?videoFoam=true",
+            r#"<div class="wistia_embed wistia_async_abc1234567 videoFoam=true"></div>"#,
+        ] {
+            assert_eq!(MediaHashedId::from(input), "abc1234567", "{input}");
+        }
+    }
+
+    #[test]
+    fn test_serde() {
+        let id: ProjectHashedId = serde_json::from_str(r#""xyz1234567""#).unwrap();
+        assert_eq!(id, "xyz1234567");
+        assert_eq!(serde_json::to_string(&id).unwrap(), r#""xyz1234567""#);
+
+        let err = serde_json::from_str::<MediaHashedId>(r#""abc123""#).unwrap_err();
+        assert!(err.to_string().contains("media hashed id"));
+
+        let id: AccountId = serde_json::from_str("123456789").unwrap();
+        assert_eq!(serde_json::to_string(&id).unwrap(), "123456789");
+    }
+}
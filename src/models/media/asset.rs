@@ -1,14 +1,16 @@
 use crate::utils::is_default;
 
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Asset {
     pub content_type: String,
     pub file_size: u64,
     #[serde(rename = "type")]
-    pub type_field: String,
+    pub type_field: AssetType,
     pub url: String,
     /// Note: `height` will **not** be populated for audio (or alternate audio) files
     #[serde(skip_serializing_if = "is_default")]
@@ -18,4 +20,158 @@ pub struct Asset {
     #[serde(skip_serializing_if = "is_default")]
     #[serde(default)]
     pub width: u64,
+    /// Fields not (yet) modeled; see [unknown fields](crate::models#unknown-fields).
+    #[cfg_attr(not(feature = "strict"), serde(flatten))]
+    #[cfg_attr(feature = "strict", serde(skip))]
+    pub extra: Map<String, Value>,
+}
+
+impl Asset {
+    /// Checks if the asset is an audio-only file.
+    pub fn is_audio(&self) -> bool {
+        self.type_field.is_audio() || self.content_type.starts_with("audio/")
+    }
+
+    /// Checks if the asset is a playable video rendition, i.e. *not* a
+    /// storyboard or a still image.
+    pub fn is_video(&self) -> bool {
+        self.content_type.starts_with("video/")
+            && !matches!(
+                self.type_field,
+                AssetType::StoryboardFile | AssetType::StillImageFile
+            )
+    }
+}
+
+string_enum! {
+    /// The type of a media [asset], such as the original file that was
+    /// uploaded or one of the derivatives generated by Wistia.
+    ///
+    /// [asset]: https://wistia.com/support/developers/asset-urls
+    pub enum AssetType {
+        #[default]
+        OriginalFile => "OriginalFile",
+        FlashVideoFile => "FlashVideoFile",
+        MdFlashVideoFile => "MdFlashVideoFile",
+        HdFlashVideoFile => "HdFlashVideoFile",
+        Mp4VideoFile => "Mp4VideoFile",
+        MdMp4VideoFile => "MdMp4VideoFile",
+        HdMp4VideoFile => "HdMp4VideoFile",
+        IphoneVideoFile => "IphoneVideoFile",
+        StillImageFile => "StillImageFile",
+        StoryboardFile => "StoryboardFile",
+        AlternateAudioFile => "AlternateAudioFile",
+        Mp4AlternateAudioFile => "Mp4AlternateAudioFile",
+    }
+}
+
+impl AssetType {
+    /// Checks if the asset type is an (alternate) audio file.
+    pub fn is_audio(&self) -> bool {
+        matches!(self, Self::AlternateAudioFile | Self::Mp4AlternateAudioFile)
+    }
+}
+
+/// Criteria used to select the best [`Asset`] for a media, via
+/// [`Media::select_asset`].
+///
+/// [`Media::select_asset`]: crate::models::Media::select_asset
+///
+/// # Examples
+///
+/// ```
+/// use rust_wistia::models::AssetCriteria;
+///
+/// // The highest quality video that is at most 720p and under 50 MB
+/// let criteria = AssetCriteria::default()
+///     .max_height(720)
+///     .max_file_size(50 * 1024 * 1024);
+///
+/// // The highest quality audio-only file
+/// let criteria = AssetCriteria::audio_only();
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct AssetCriteria {
+    /// The maximum height (in pixels) of a video asset.
+    pub max_height: Option<u64>,
+    /// The maximum size (in bytes) of the asset.
+    pub max_file_size: Option<u64>,
+    /// Only select audio files, rather than videos.
+    pub audio_only: bool,
+}
+
+impl AssetCriteria {
+    /// Criteria to select the highest quality audio-only file.
+    pub fn audio_only() -> Self {
+        Self {
+            audio_only: true,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the maximum height (in pixels) of a video asset.
+    pub fn max_height(mut self, max_height: u64) -> Self {
+        self.max_height = Some(max_height);
+        self
+    }
+
+    /// Sets the maximum size (in bytes) of the asset.
+    pub fn max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = Some(max_file_size);
+        self
+    }
+
+    /// Checks if an asset satisfies the criteria.
+    pub fn matches(&self, asset: &Asset) -> bool {
+        let is_kind = if self.audio_only {
+            asset.is_audio()
+        } else {
+            asset.is_video() && asset.height != 0
+        };
+
+        is_kind
+            && self.max_height.into_iter().all(|h| asset.height <= h)
+            && self.max_file_size.into_iter().all(|s| asset.file_size <= s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::from_str;
+
+    #[test]
+    fn test_deserialize_asset_type() {
+        let asset: Asset = from_str(
+            r#"{"url": "http://embed.wistia.com/deliveries/abc.bin", "width": 1280, "height": 720, "fileSize": 1024, "contentType": "video/mp4", "type": "HdMp4VideoFile"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(asset.type_field, AssetType::HdMp4VideoFile);
+        assert!(asset.is_video());
+
+        let asset: Asset = from_str(
+            r#"{"url": "http://embed.wistia.com/deliveries/xyz.bin", "fileSize": 1024, "contentType": "video/mp4", "type": "SomeNewFile"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(asset.type_field, AssetType::Unknown("SomeNewFile".into()));
+        assert_eq!(
+            serde_json::to_value(&asset).unwrap()["type"],
+            serde_json::json!("SomeNewFile")
+        );
+    }
+
+    #[test]
+    fn test_asset_type_round_trip() {
+        for s in [
+            "OriginalFile",
+            "IphoneVideoFile",
+            "Mp4AlternateAudioFile",
+            "Foo",
+        ] {
+            assert_eq!(AssetType::from(s).as_str(), s);
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use crate::models::Media;
+use crate::models::{AssetType, Media, MediaHashedId};
 use std::path::{Path, PathBuf};
 
 /// Represents a request to download an [Asset URL].
@@ -6,9 +6,9 @@ use std::path::{Path, PathBuf};
 /// [Asset URL]: https://wistia.com/support/developers/asset-urls
 ///
 #[derive(Default)]
-pub struct DownloadAssetRequest<'a> {
+pub struct DownloadAssetRequest {
     /// The Wistia media to download.
-    pub media_id: Option<&'a str>,
+    pub media_id: Option<MediaHashedId>,
 
     /// The Wistia media to download.
     ///
@@ -24,25 +24,29 @@ pub struct DownloadAssetRequest<'a> {
     pub media: Option<Media>,
 
     /// The media *asset type* to download.
-    pub asset_type: Option<&'a str>,
+    pub asset_type: Option<AssetType>,
 
     /// Sets a local *file path* to save the downloaded media content to.
     pub file_path: Option<PathBuf>,
 }
 
-impl<'a> From<&'a str> for DownloadAssetRequest<'a> {
+impl From<&str> for DownloadAssetRequest {
     /// Create a new `DownloadAssetRequest` from a Wistia `media_id`
-    fn from(media_id: &'a str) -> Self {
-        Self {
-            media_id: Some(media_id),
-            ..Default::default()
-        }
+    fn from(media_id: &str) -> Self {
+        Self::from(MediaHashedId::from(media_id))
     }
 }
 
-impl<'a> From<&'a String> for DownloadAssetRequest<'a> {
+impl From<&String> for DownloadAssetRequest {
     /// Create a new `DownloadAssetRequest` from a Wistia `media_id`
-    fn from(media_id: &'a String) -> Self {
+    fn from(media_id: &String) -> Self {
+        Self::from(MediaHashedId::from(media_id))
+    }
+}
+
+impl From<MediaHashedId> for DownloadAssetRequest {
+    /// Create a new `DownloadAssetRequest` from a Wistia `media_id`
+    fn from(media_id: MediaHashedId) -> Self {
         Self {
             media_id: Some(media_id),
             ..Default::default()
@@ -50,7 +54,7 @@ impl<'a> From<&'a String> for DownloadAssetRequest<'a> {
     }
 }
 
-impl<'a> From<Media> for DownloadAssetRequest<'a> {
+impl From<Media> for DownloadAssetRequest {
     /// Create a new `DownloadAssetRequest` from a `Media` object
     fn from(media: Media) -> Self {
         Self {
@@ -60,18 +64,18 @@ impl<'a> From<Media> for DownloadAssetRequest<'a> {
     }
 }
 
-impl<'a> DownloadAssetRequest<'a> {
+impl DownloadAssetRequest {
     /// Sets the media *asset type* to download from Wistia.
     ///
     /// # Examples
     ///
     /// ```
-    /// use rust_wistia::models::DownloadAssetRequest;
+    /// use rust_wistia::models::{AssetType, DownloadAssetRequest};
     ///
-    /// let info = DownloadAssetRequest::from("my-video-id").asset_type("OriginalFile");
+    /// let info = DownloadAssetRequest::from("my-video-id").asset_type(AssetType::HdMp4VideoFile);
     /// ```    
-    pub fn asset_type(mut self, asset_type: &'a str) -> Self {
-        self.asset_type = Some(asset_type);
+    pub fn asset_type(mut self, asset_type: impl Into<AssetType>) -> Self {
+        self.asset_type = Some(asset_type.into());
         self
     }
 
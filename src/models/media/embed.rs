@@ -0,0 +1,294 @@
+use crate::models::MediaHashedId;
+use crate::{Result, RustWistiaError};
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// The kind of [embed] that a Wistia URL or embed code refers to.
+///
+/// [embed]: https://wistia.com/support/embed-and-share/embed-options
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmbedType {
+    /// A media page on the account, such as `https://my-account.wistia.com/medias/<id>`
+    MediaPage,
+    /// An iframe embed, such as `https://fast.wistia.net/embed/iframe/<id>`
+    Iframe,
+    /// A standard (inline) embed, such as `<div class="wistia_embed wistia_async_<id>">`
+    Async,
+    /// A popover embed, which opens the video in a lightbox when clicked.
+    Popover,
+}
+
+/// Info on a Wistia media, as parsed from a URL or embed code.
+///
+/// # Examples
+///
+/// ```
+/// use rust_wistia::models::{EmbedInfo, EmbedType};
+///
+/// let info = EmbedInfo::parse("https://fast.wistia.net/embed/iframe/abc1234567?videoFoam=true").unwrap();
+///
+/// assert_eq!(info.hashed_id, "abc1234567");
+/// assert_eq!(info.embed_type, EmbedType::Iframe);
+/// assert_eq!(info.options["videoFoam"], "true");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbedInfo {
+    /// The hashed ID of the media (example: `abc1234567`).
+    pub hashed_id: MediaHashedId,
+    /// The type of embed which was parsed.
+    pub embed_type: EmbedType,
+    /// The [embed options] that were set, such as `videoFoam` or `autoPlay`.
+    ///
+    /// [embed options]: https://wistia.com/support/developers/embed-options
+    pub options: BTreeMap<String, String>,
+}
+
+impl FromStr for EmbedInfo {
+    type Err = RustWistiaError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl EmbedInfo {
+    /// Parse a Wistia media URL, iframe embed code, standard (async) embed
+    /// code or popover embed code, and return the media *hashed ID* along
+    /// with the embed type and options.
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+
+        Self::parse_async(input)
+            .or_else(|| Self::parse_url(input, "/embed/iframe/", EmbedType::Iframe))
+            .or_else(|| Self::parse_url(input, "/embed/medias/", EmbedType::Async))
+            .or_else(|| Self::parse_url(input, "/medias/", EmbedType::MediaPage))
+            .ok_or_else(|| RustWistiaError::UnrecognizedEmbed {
+                input: input.to_owned(),
+            })
+    }
+
+    /// Parse a standard or popover embed code, where the hashed ID and the
+    /// embed options are set in a `class` attribute, such as:
+    ///   `wistia_embed wistia_async_abc1234567 popover=true videoFoam=true`
+    fn parse_async(input: &str) -> Option<Self> {
+        const PREFIX: &str = "wistia_async_";
+
+        let start = input.find(PREFIX)?;
+        let hashed_id = take_id(&input[start + PREFIX.len()..])?;
+
+        // Limit the options to the enclosing `class` attribute, if any.
+        let before = &input[..start];
+        let after = &input[start..];
+        let class_start = before.rfind(['"', '\'']).map_or(0, |i| i + 1);
+        let class_end = after.find(['"', '\'']).map_or(input.len(), |i| start + i);
+
+        let options: BTreeMap<String, String> = input[class_start..class_end]
+            .split_whitespace()
+            .filter_map(|token| token.split_once('='))
+            .map(|(k, v)| (k.to_owned(), v.to_owned()))
+            .collect();
+
+        let embed_type = if is_popover(&options) {
+            EmbedType::Popover
+        } else {
+            EmbedType::Async
+        };
+
+        Some(Self {
+            hashed_id,
+            embed_type,
+            options,
+        })
+    }
+
+    /// Parse a URL (or an embed code containing a URL) on a Wistia domain,
+    /// where the hashed ID directly follows the `marker` in the path.
+    fn parse_url(input: &str, marker: &str, embed_type: EmbedType) -> Option<Self> {
+        let start = input.find(marker)?;
+
+        if !is_wistia_host(&input[..start]) {
+            return None;
+        }
+
+        let rest = &input[start + marker.len()..];
+        let hashed_id = take_id(rest)?;
+
+        // The URL ends at the first quote or whitespace character.
+        let url_end = rest
+            .find(|c: char| c == '"' || c == '\'' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let url_rest = &rest[..url_end];
+
+        let options: BTreeMap<String, String> = match url_rest.split_once('?') {
+            Some((_, query)) => {
+                let query = query.split('#').next().unwrap_or_default();
+                let query = query.replace("&amp;", "&");
+                serde_urlencoded::from_str(&query).ok()?
+            }
+            None => BTreeMap::new(),
+        };
+
+        // Legacy popover embeds are iframe links with a `wistia-popover` class.
+        let embed_type = if is_popover(&options)
+            || (embed_type == EmbedType::Iframe && input.contains("wistia-popover"))
+        {
+            EmbedType::Popover
+        } else {
+            embed_type
+        };
+
+        Some(Self {
+            hashed_id,
+            embed_type,
+            options,
+        })
+    }
+}
+
+/// Returns the hashed ID at the start of `s`, if it is a valid (10-character,
+/// lowercase alphanumeric) hashed ID which is not followed by another
+/// alphanumeric character.
+fn take_id(s: &str) -> Option<MediaHashedId> {
+    let end = s
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(s.len());
+
+    MediaHashedId::new(&s[..end]).ok()
+}
+
+/// Checks if the text preceding a URL path ends with a Wistia host, such
+/// as `fast.wistia.net` or `my-account.wistia.com`.
+///
+/// The host must be `wistia.com`, `wistia.net` or `wi.st`, or a subdomain
+/// of one of these, so that a look-alike such as `evilwistia.com` (or a
+/// Wistia domain within the path or query of another URL) is not matched.
+fn is_wistia_host(before_path: &str) -> bool {
+    let is_delimiter = |c: char| c == '"' || c == '\'' || c.is_whitespace();
+
+    let start = before_path
+        .rfind(|c: char| c == '/' || is_delimiter(c))
+        .map_or(0, |i| i + 1);
+    let (prefix, host) = before_path.split_at(start);
+
+    // The host follows a `//`, or starts a (scheme-less) URL.
+    if !(prefix.is_empty() || prefix.ends_with("//") || prefix.ends_with(is_delimiter)) {
+        return false;
+    }
+
+    let host = host.split(':').next().unwrap_or_default();
+    if !host
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+    {
+        return false;
+    }
+    let host = host.to_ascii_lowercase();
+
+    ["wistia.com", "wistia.net", "wi.st"].iter().any(|domain| {
+        host == *domain
+            || host
+                .strip_suffix(domain)
+                .is_some_and(|subdomain| subdomain.ends_with('.'))
+    })
+}
+
+fn is_popover(options: &BTreeMap<String, String>) -> bool {
+    options.get("popover").map(String::as_str) == Some("true")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_media_page() {
+        let info = EmbedInfo::parse("https://my-account.wistia.com/medias/abc1234567").unwrap();
+
+        assert_eq!(info.hashed_id, "abc1234567");
+        assert_eq!(info.embed_type, EmbedType::MediaPage);
+        assert!(info.options.is_empty());
+    }
+
+    #[test]
+    fn test_parse_wistia_hosts() {
+        for input in [
+            "https://wistia.com/medias/abc1234567",
+            "https://My-Account.Wistia.com/medias/abc1234567",
+            "http://fast.wistia.net:443/embed/iframe/abc1234567",
+            "fast.wistia.net/embed/iframe/abc1234567",
+            "https://wi.st/medias/abc1234567",
+        ] {
+            let info = EmbedInfo::parse(input).unwrap();
+            assert_eq!(info.hashed_id, "abc1234567", "{input}");
+        }
+    }
+
+    #[test]
+    fn test_parse_iframe() {
+        let code = r#"<iframe src="https://fast.wistia.net/embed/iframe/abc1234567?videoFoam=true&amp;playerColor=ff0000" allowtransparency="true" class="wistia_embed" name="wistia_embed" width="640" height="360"></iframe>"#;
+        let info = EmbedInfo::parse(code).unwrap();
+
+        assert_eq!(info.hashed_id, "abc1234567");
+        assert_eq!(info.embed_type, EmbedType::Iframe);
+        assert_eq!(info.options["videoFoam"], "true");
+        assert_eq!(info.options["playerColor"], "ff0000");
+    }
+
+    #[test]
+    fn test_parse_async() {
+        let code = r#"<script src="https://fast.wistia.com/embed/medias/abc1234567.jsonp" async></script><script src="https://fast.wistia.com/assets/external/E-v1.js" async></script><div class="wistia_embed wistia_async_abc1234567 videoFoam=true autoPlay=false" style="height:360px;width:640px">&nbsp;</div>"#;
+        let info = EmbedInfo::parse(code).unwrap();
+
+        assert_eq!(info.hashed_id, "abc1234567");
+        assert_eq!(info.embed_type, EmbedType::Async);
+        assert_eq!(info.options.len(), 2);
+        assert_eq!(info.options["autoPlay"], "false");
+    }
+
+    #[test]
+    fn test_parse_async_snippet() {
+        let info: EmbedInfo = "wistia_embed wistia_async_abc1234567".parse().unwrap();
+
+        assert_eq!(info.hashed_id, "abc1234567");
+        assert_eq!(info.embed_type, EmbedType::Async);
+    }
+
+    #[test]
+    fn test_parse_popover() {
+        let code = r#"<span class="wistia_embed wistia_async_abc1234567 popover=true popoverAnimateThumbnail=true" style="display:inline-block;height:84px;width:150px">&nbsp;</span>"#;
+        let info = EmbedInfo::parse(code).unwrap();
+
+        assert_eq!(info.hashed_id, "abc1234567");
+        assert_eq!(info.embed_type, EmbedType::Popover);
+        assert_eq!(info.options["popoverAnimateThumbnail"], "true");
+
+        let code = r#"<a href="//fast.wistia.net/embed/iframe/abc1234567?popover=true" class="wistia-popover[height=360,playerColor=7b796a,width=640]"><img src="https://embed-ssl.wistia.com/deliveries/xyz.jpg"/></a>"#;
+        let info = EmbedInfo::parse(code).unwrap();
+
+        assert_eq!(info.hashed_id, "abc1234567");
+        assert_eq!(info.embed_type, EmbedType::Popover);
+    }
+
+    #[test]
+    fn test_parse_unrecognized() {
+        for input in [
+            "",
+            "abc1234567",
+            "https://example.com/medias/abc1234567",
+            "https://fast.wistia.net/embed/iframe/",
+            "https://evilwistia.com/medias/abc1234567",
+            "https://fast.notwistia.net/embed/iframe/abc1234567",
+            "https://wistia.com.example.com/medias/abc1234567",
+            "https://example.com/?next=.wistia.net/embed/iframe/abc1234567",
+            "https://example.com/wistia.com/medias/abc1234567",
+            "wistia_embed wistia_async_abc",
+            "wistia_embed wistia_async_abc12345678",
+            "https://fast.wistia.net/embed/iframe/ABC1234567",
+            "https://my-account.wistia.com/medias/abc123",
+        ] {
+            let err = EmbedInfo::parse(input).unwrap_err();
+            assert!(matches!(err, RustWistiaError::UnrecognizedEmbed { .. }));
+        }
+    }
+}
@@ -1,23 +1,41 @@
-use super::{Asset, MediaStatus, MediaType, ProjectInfo, Thumbnail};
+use super::{Asset, AssetCriteria, AssetType, MediaStatus, MediaType, ProjectInfo, Thumbnail};
 use crate::constants::*;
+#[cfg(any(feature = "chrono", feature = "time"))]
+use crate::models::timestamp::seconds;
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+use crate::models::timestamp::to_duration;
+use crate::models::timestamp::{default_timestamp, rfc3339, to_system_time, Timestamp};
+use crate::models::{MediaHashedId, MediaId};
 use crate::{Result, RustWistiaError};
 
+use std::time::{Duration, SystemTime};
+
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-#[derive(Default, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Media {
     #[serde(rename = "hashed_id")]
-    pub hashed_id: String,
-    pub id: u64,
+    pub hashed_id: MediaHashedId,
+    pub id: MediaId,
     pub name: String,
     #[serde(rename = "type")]
     pub type_field: MediaType,
-    pub created: String,
-    pub updated: String,
+    #[serde(with = "rfc3339")]
+    pub created: Timestamp,
+    #[serde(with = "rfc3339")]
+    pub updated: Timestamp,
     /// Note: only videos have this attribute set; thumbnails and other
     /// medias don't.
+    #[cfg(not(any(feature = "chrono", feature = "time")))]
     pub duration: Option<f64>,
+    /// Note: only videos have this attribute set; thumbnails and other
+    /// medias don't.
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    #[serde(default, with = "seconds::option")]
+    pub duration: Option<Duration>,
     pub status: MediaStatus,
     #[serde(default)]
     pub description: String,
@@ -32,9 +50,62 @@ pub struct Media {
     pub section: Option<String>,
     #[serde(default)]
     pub archived: bool,
+    /// Fields not (yet) modeled; see [unknown fields](crate::models#unknown-fields).
+    #[cfg_attr(not(feature = "strict"), serde(flatten))]
+    #[cfg_attr(feature = "strict", serde(skip))]
+    pub extra: Map<String, Value>,
+}
+
+// Not derived, as the timestamps have no default with the `time` feature.
+impl Default for Media {
+    fn default() -> Self {
+        Self {
+            hashed_id: Default::default(),
+            id: Default::default(),
+            name: Default::default(),
+            type_field: Default::default(),
+            created: default_timestamp(),
+            updated: default_timestamp(),
+            duration: Default::default(),
+            status: Default::default(),
+            description: Default::default(),
+            progress: Default::default(),
+            thumbnail: Default::default(),
+            project: Default::default(),
+            embed_code: Default::default(),
+            assets: Default::default(),
+            section: Default::default(),
+            archived: Default::default(),
+            extra: Default::default(),
+        }
+    }
 }
 
 impl Media {
+    /// Returns when the media was created, or `None` if the timestamp could
+    /// not be parsed.
+    pub fn created_at(&self) -> Option<SystemTime> {
+        to_system_time(&self.created)
+    }
+
+    /// Returns when the media (or its metadata) was last updated, or `None`
+    /// if the timestamp could not be parsed.
+    pub fn updated_at(&self) -> Option<SystemTime> {
+        to_system_time(&self.updated)
+    }
+
+    /// Returns the length of the media, if it is a video or audio file.
+    #[cfg(not(any(feature = "chrono", feature = "time")))]
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration.and_then(to_duration)
+    }
+
+    /// Returns the length of the media, if it is a video or audio file.
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration
+    }
+
     /// Retrieve the *asset URL* for the original source media that was uploaded.
     pub fn source_url(&self) -> Result<String> {
         self.asset_url(None)
@@ -58,22 +129,166 @@ impl Media {
 
     /// Retrieve the *asset URL* (default: **HTTP**) for a specified `asset_type`, which
     /// defaults to the original source media if not provided.
+    ///
+    /// The `asset_type` is the name of an [`AssetType`], such as `OriginalFile`
+    /// or `AssetType::HdMp4VideoFile.as_str()`.
     pub fn asset_url_insecure<'a>(
         &'a self,
         asset_type: impl Into<Option<&'a str>>,
     ) -> Result<&'a str> {
-        let r#type = asset_type.into().unwrap_or(ORIGINAL_ASSET);
+        let r#type = asset_type.into().map(AssetType::from).unwrap_or_default();
 
-        for asset in self.assets.iter() {
-            if asset.type_field == r#type {
-                return Ok(&asset.url);
-            }
+        match self.asset(&r#type) {
+            Some(asset) => Ok(&asset.url),
+            None => Err(RustWistiaError::AssetNotFound {
+                r#type: r#type.to_string(),
+                video_id: self.hashed_id.to_string(),
+                valid_types: self
+                    .assets
+                    .iter()
+                    .map(|a| a.type_field.to_string())
+                    .collect(),
+            }),
         }
+    }
+
+    /// Retrieve the asset for a specified `asset_type`, if one exists.
+    pub fn asset(&self, asset_type: &AssetType) -> Option<&Asset> {
+        self.assets.iter().find(|a| &a.type_field == asset_type)
+    }
+
+    /// Select the best asset which satisfies the `criteria`.
+    ///
+    /// For videos, this is the asset with the greatest height, preferring
+    /// MP4 files over other formats; for audio, this is the largest file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use rust_wistia::models::AssetCriteria;
+    /// use rust_wistia::WistiaClient;
+    ///
+    /// let media = WistiaClient::from_env()?.get_media("my-id").await?;
+    /// let asset = media.select_asset(&AssetCriteria::default().max_height(720));
+    /// ```
+    pub fn select_asset(&self, criteria: &AssetCriteria) -> Option<&Asset> {
+        self.assets
+            .iter()
+            .filter(|a| criteria.matches(a))
+            .max_by_key(|a| (a.height, a.content_type == "video/mp4", a.file_size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::fixtures::{with_fields, MEDIA_JSON};
+
+    use serde_json::json;
+
+    fn asset(r#type: AssetType, content_type: &str, height: u64, file_size: u64) -> Asset {
+        Asset {
+            content_type: content_type.to_owned(),
+            file_size,
+            type_field: r#type,
+            height,
+            ..Default::default()
+        }
+    }
+
+    fn media() -> Media {
+        Media {
+            assets: vec![
+                asset(AssetType::OriginalFile, "video/quicktime", 1080, 900),
+                asset(AssetType::FlashVideoFile, "video/x-flv", 720, 300),
+                asset(AssetType::HdMp4VideoFile, "video/mp4", 720, 400),
+                asset(AssetType::IphoneVideoFile, "video/mp4", 360, 100),
+                asset(AssetType::StoryboardFile, "video/mp4", 1800, 10),
+                asset(AssetType::AlternateAudioFile, "audio/mp4", 0, 50),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_select_asset() {
+        let media = media();
+
+        let best = media.select_asset(&AssetCriteria::default()).unwrap();
+        assert_eq!(best.type_field, AssetType::OriginalFile);
+
+        let best = media
+            .select_asset(&AssetCriteria::default().max_height(720))
+            .unwrap();
+        assert_eq!(best.type_field, AssetType::HdMp4VideoFile);
+
+        let best = media
+            .select_asset(&AssetCriteria::default().max_file_size(200))
+            .unwrap();
+        assert_eq!(best.type_field, AssetType::IphoneVideoFile);
+
+        let best = media.select_asset(&AssetCriteria::audio_only()).unwrap();
+        assert_eq!(best.type_field, AssetType::AlternateAudioFile);
+
+        assert!(media
+            .select_asset(&AssetCriteria::default().max_height(240))
+            .is_none());
+    }
+
+    #[test]
+    fn test_deserialize_media() {
+        let media: Media = serde_json::from_str(MEDIA_JSON).unwrap();
+
+        assert_eq!(media.hashed_id, "v80gyfkt28");
+        assert_eq!(media.type_field, MediaType::Video);
+        assert_eq!(media.status, MediaStatus::Ready);
+        assert_eq!(media.assets[0].type_field, AssetType::OriginalFile);
+    }
+
+    #[test]
+    fn test_deserialize_unknown_type_and_status() {
+        let data = with_fields(
+            MEDIA_JSON,
+            json!({"type": "HologramVideo", "status": "archiving"}),
+        );
+
+        let media: Media = serde_json::from_value(data).unwrap();
+
+        assert_eq!(media.type_field, MediaType::Unknown("HologramVideo".into()));
+        assert_eq!(media.status, MediaStatus::Unknown("archiving".into()));
+
+        let value = serde_json::to_value(&media).unwrap();
+
+        assert_eq!(value["type"], "HologramVideo");
+        assert_eq!(value["status"], "archiving");
+
+        // Wistia's own `UnknownType` is a known value
+        let data = with_fields(MEDIA_JSON, json!({"type": "UnknownType"}));
+        let media: Media = serde_json::from_value(data).unwrap();
+
+        assert_eq!(media.type_field, MediaType::UnknownType);
+    }
+
+    #[test]
+    fn test_asset_not_found() {
+        let err = media()
+            .asset_url(AssetType::MdMp4VideoFile.as_str())
+            .unwrap_err();
+
+        assert!(matches!(err, RustWistiaError::AssetNotFound { .. }));
+    }
+
+    #[test]
+    fn test_asset_url() {
+        let mut media = media();
+        media.assets[0].url = "http://embed.wistia.com/deliveries/abc123.bin".to_owned();
 
-        Err(RustWistiaError::AssetNotFound {
-            r#type: r#type.to_string(),
-            video_id: self.hashed_id.clone(),
-            valid_types: self.assets.iter().map(|a| a.type_field.clone()).collect(),
-        })
+        let url = "https://embed-ssl.wistia.com/deliveries/abc123/file.mp4";
+        assert_eq!(media.asset_url("OriginalFile").unwrap(), url);
+        assert_eq!(media.asset_url(None).unwrap(), url);
+        assert_eq!(
+            media.asset_url_insecure(Some("OriginalFile")).unwrap(),
+            "http://embed.wistia.com/deliveries/abc123.bin"
+        );
     }
 }
@@ -1,20 +1,39 @@
 use super::{MediaStatus, MediaType, Thumbnail};
+#[cfg(any(feature = "chrono", feature = "time"))]
+use crate::models::timestamp::seconds;
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+use crate::models::timestamp::to_duration;
+use crate::models::timestamp::{default_timestamp, rfc3339, to_system_time, Timestamp};
+use crate::models::{MediaHashedId, MediaId};
+
+use std::time::{Duration, SystemTime};
+
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-#[derive(Default, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct MediaInfo {
     #[serde(rename = "hashed_id")]
-    pub hashed_id: String,
-    pub id: u64,
+    pub hashed_id: MediaHashedId,
+    pub id: MediaId,
     pub name: String,
     #[serde(rename = "type")]
     pub type_field: MediaType,
-    pub created: String,
-    pub updated: String,
+    #[serde(with = "rfc3339")]
+    pub created: Timestamp,
+    #[serde(with = "rfc3339")]
+    pub updated: Timestamp,
     /// Note: only videos have this attribute set; thumbnails and other
     /// medias don't.
+    #[cfg(not(any(feature = "chrono", feature = "time")))]
     pub duration: Option<f64>,
+    /// Note: only videos have this attribute set; thumbnails and other
+    /// medias don't.
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    #[serde(default, with = "seconds::option")]
+    pub duration: Option<Duration>,
     pub status: MediaStatus,
     pub description: String,
     pub progress: f64,
@@ -22,4 +41,89 @@ pub struct MediaInfo {
     pub section: Option<String>,
     #[serde(default)]
     pub archived: bool,
+    /// Fields not (yet) modeled; see [unknown fields](crate::models#unknown-fields).
+    #[cfg_attr(not(feature = "strict"), serde(flatten))]
+    #[cfg_attr(feature = "strict", serde(skip))]
+    pub extra: Map<String, Value>,
+}
+
+// Not derived, as the timestamps have no default with the `time` feature.
+impl Default for MediaInfo {
+    fn default() -> Self {
+        Self {
+            hashed_id: Default::default(),
+            id: Default::default(),
+            name: Default::default(),
+            type_field: Default::default(),
+            created: default_timestamp(),
+            updated: default_timestamp(),
+            duration: Default::default(),
+            status: Default::default(),
+            description: Default::default(),
+            progress: Default::default(),
+            thumbnail: Default::default(),
+            section: Default::default(),
+            archived: Default::default(),
+            extra: Default::default(),
+        }
+    }
+}
+
+impl MediaInfo {
+    /// Returns when the media was created, or `None` if the timestamp could
+    /// not be parsed.
+    pub fn created_at(&self) -> Option<SystemTime> {
+        to_system_time(&self.created)
+    }
+
+    /// Returns when the media (or its metadata) was last updated, or `None`
+    /// if the timestamp could not be parsed.
+    pub fn updated_at(&self) -> Option<SystemTime> {
+        to_system_time(&self.updated)
+    }
+
+    /// Returns the length of the media, if it is a video or audio file.
+    #[cfg(not(any(feature = "chrono", feature = "time")))]
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration.and_then(to_duration)
+    }
+
+    /// Returns the length of the media, if it is a video or audio file.
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::fixtures::{with_fields, MEDIA_JSON};
+
+    use serde_json::json;
+
+    #[test]
+    fn test_deserialize_unknown_type_and_status() {
+        // The response of `Medias#Update` omits the project and assets
+        let data = with_fields(
+            MEDIA_JSON,
+            json!({
+                "type": "Playlist",
+                "status": "transcoding",
+                "project": null,
+                "embedCode": null,
+                "assets": null,
+            }),
+        );
+
+        let info: MediaInfo = serde_json::from_value(data).unwrap();
+
+        assert_eq!(info.type_field, MediaType::Unknown("Playlist".into()));
+        assert_eq!(info.status, MediaStatus::Unknown("transcoding".into()));
+
+        let value = serde_json::to_value(&info).unwrap();
+
+        assert_eq!(value["type"], "Playlist");
+        assert_eq!(value["status"], "transcoding");
+    }
 }
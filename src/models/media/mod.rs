@@ -1,5 +1,103 @@
+/// Define an `enum` for a string value in a response from Wistia, such as
+/// the type of a media, along with an `Unknown(String)` variant which holds
+/// any value not (yet) known to this library.
+///
+/// This implements `as_str()`, `Display`, `FromStr` and conversions from
+/// strings, along with `Serialize` and `Deserialize` which preserve the raw
+/// value of an `Unknown` variant.
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident => $value:literal,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )*
+            /// Any other value, which is not (yet) known to this library. The
+            /// raw value is preserved when re-serialized.
+            Unknown(String),
+        }
+
+        impl $name {
+            /// Returns the value, as it appears in a response from Wistia.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)*
+                    Self::Unknown(s) => s,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(s: &str) -> Self {
+                match s {
+                    $($value => Self::$variant,)*
+                    other => Self::Unknown(other.to_owned()),
+                }
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(s: String) -> Self {
+                match s.as_str() {
+                    $($value => Self::$variant,)*
+                    _ => Self::Unknown(s),
+                }
+            }
+        }
+
+        impl From<&String> for $name {
+            fn from(s: &String) -> Self {
+                Self::from(s.as_str())
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = std::convert::Infallible;
+
+            fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+                Ok(Self::from(s))
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> std::result::Result<Self, D::Error> {
+                String::deserialize(deserializer).map(Self::from)
+            }
+        }
+    };
+}
+
 mod asset;
 mod download_asset;
+mod embed;
+#[allow(clippy::module_inception)]
 mod media;
 mod media_info;
 mod project;
@@ -10,6 +108,7 @@ mod update_request;
 
 pub use asset::*;
 pub use download_asset::*;
+pub use embed::*;
 pub use media::*;
 pub use media_info::*;
 pub use project::*;
@@ -1,8 +1,16 @@
+use crate::models::{ProjectHashedId, ProjectId};
+
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct ProjectInfo {
-    pub hashed_id: String,
-    pub id: u64,
+    pub hashed_id: ProjectHashedId,
+    pub id: ProjectId,
     pub name: String,
+    /// Fields not (yet) modeled; see [unknown fields](crate::models#unknown-fields).
+    #[cfg_attr(not(feature = "strict"), serde(flatten))]
+    #[cfg_attr(feature = "strict", serde(skip))]
+    pub extra: Map<String, Value>,
 }
@@ -1,11 +1,17 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 /// An object representing the [thumbnail] for a media.
 ///
 /// [thumbnail]: https://wistia.com/support/developers/data-api#medias-response
 #[derive(Default, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Thumbnail {
     pub url: String,
     pub width: u64,
     pub height: u64,
+    /// Fields not (yet) modeled; see [unknown fields](crate::models#unknown-fields).
+    #[cfg_attr(not(feature = "strict"), serde(flatten))]
+    #[cfg_attr(feature = "strict", serde(skip))]
+    pub extra: Map<String, Value>,
 }
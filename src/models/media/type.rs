@@ -1,23 +1,20 @@
-use serde::{Deserialize, Serialize};
-
-/// A value which represents the type of media.
-///
-/// See more [on `type`][].
-///
-/// [on `type`]: https://wistia.com/support/developers/data-api#medias-response
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
-pub enum MediaType {
-    Video,
-    Audio,
-    Image,
-    PdfDocument,
-    MicrosoftOfficeDocument,
-    Swf,
-    UnknownType,
-}
-
-impl Default for MediaType {
-    fn default() -> Self {
-        MediaType::Video
+string_enum! {
+    /// A value which represents the type of media.
+    ///
+    /// See more [on `type`][].
+    ///
+    /// [on `type`]: https://wistia.com/support/developers/data-api#medias-response
+    pub enum MediaType {
+        #[default]
+        Video => "Video",
+        Audio => "Audio",
+        Image => "Image",
+        PdfDocument => "PdfDocument",
+        MicrosoftOfficeDocument => "MicrosoftOfficeDocument",
+        Swf => "Swf",
+        /// The `UnknownType` which Wistia itself returns for a media it could
+        /// not identify. This differs from [`MediaType::Unknown`], which holds
+        /// a type that is not (yet) known to this library.
+        UnknownType => "UnknownType",
     }
 }
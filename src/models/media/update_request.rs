@@ -1,3 +1,5 @@
+use crate::models::MediaHashedId;
+
 use serde::Serialize;
 
 /// Represents a [Medias: Update] request.
@@ -8,7 +10,7 @@ use serde::Serialize;
 pub struct UpdateMediaRequest {
     /// The hashed Video Id (example: `abc1234567`).
     #[serde(skip_serializing)]
-    pub id: String,
+    pub id: MediaHashedId,
     /// The media's new name.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -16,13 +18,21 @@ pub struct UpdateMediaRequest {
     /// displayed before the player starts playing. Will return failure message
     /// unless media to update is a video, and new still is an image.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub new_still_media_id: Option<String>,
+    pub new_still_media_id: Option<MediaHashedId>,
     /// A new description for this media. Accepts plain text or markdown.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 }
 
 impl UpdateMediaRequest {
+    /// Create a new request to update a media with the hashed ID `id`
+    pub fn new(id: impl Into<MediaHashedId>) -> Self {
+        Self {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+
     /// Sets the media's new name
     pub fn name(mut self, name: &str) -> Self {
         self.name = Some(name.to_owned());
@@ -31,8 +41,8 @@ impl UpdateMediaRequest {
 
     /// Sets the Wistia hashed ID of an image that will replace the still that’s
     /// displayed before the player starts playing.
-    pub fn new_still_media_id(mut self, new_still_media_id: &str) -> Self {
-        self.new_still_media_id = Some(new_still_media_id.to_owned());
+    pub fn new_still_media_id(mut self, new_still_media_id: impl Into<MediaHashedId>) -> Self {
+        self.new_still_media_id = Some(new_still_media_id.into());
         self
     }
 
@@ -1,6 +1,28 @@
+//! The request and response models of the Wistia API.
+//!
+//! # Unknown fields
+//!
+//! The response models have an `extra` field, which captures any fields in
+//! the response that are not (yet) modeled by this library, so that these
+//! are not lost when a model is re-serialized.
+//!
+//! With the optional `strict` feature, an unknown field is instead an error
+//! when de-serializing a response, and `extra` is always empty. This is
+//! mainly useful for contract testing against the Wistia API.
+//!
 pub mod error;
+#[cfg(test)]
+mod fixtures;
+mod ids;
 mod media;
+mod raw;
+mod stats;
+mod timestamp;
 mod upload;
 
+pub use ids::*;
 pub use media::*;
+pub use raw::*;
+pub use stats::*;
+pub use timestamp::Timestamp;
 pub use upload::*;
@@ -0,0 +1,70 @@
+use crate::Result;
+
+use hyper::{HeaderMap, StatusCode};
+use serde::Serialize;
+use serde_json::Value;
+
+/// The body (or *query parameters*) to send with a request to the Wistia
+/// Data API, via one of the generic methods on the [`DataClient`].
+///
+/// [`DataClient`]: crate::DataClient
+///
+/// # Examples
+///
+/// ```
+/// use rust_wistia::models::RequestBody;
+///
+/// let body = RequestBody::form([("name", "My Video Name")]).unwrap();
+/// assert_eq!(body, RequestBody::Form("name=My+Video+Name".to_owned()));
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub enum RequestBody {
+    /// No request body or query parameters.
+    #[default]
+    Empty,
+    /// URL-encoded *query parameters*, which are appended to the request URL.
+    Query(String),
+    /// An `application/x-www-form-urlencoded` request body.
+    Form(String),
+    /// An `application/json` request body.
+    Json(Vec<u8>),
+}
+
+impl RequestBody {
+    /// URL-encode `params` as *query parameters*.
+    pub fn query<B: Serialize>(params: B) -> Result<Self> {
+        Ok(Self::Query(serde_urlencoded::to_string(params)?))
+    }
+
+    /// URL-encode `body` as an `application/x-www-form-urlencoded` body.
+    pub fn form<B: Serialize>(body: B) -> Result<Self> {
+        Ok(Self::Form(serde_urlencoded::to_string(body)?))
+    }
+
+    /// Serialize `body` as an `application/json` body.
+    pub fn json<B: Serialize>(body: &B) -> Result<Self> {
+        Ok(Self::Json(serde_json::to_vec(body)?))
+    }
+
+    /// Returns the `Content-Type` of the request body, if any.
+    pub fn content_type(&self) -> Option<&'static str> {
+        match self {
+            Self::Empty | Self::Query(_) => None,
+            Self::Form(_) => Some("application/x-www-form-urlencoded"),
+            Self::Json(_) => Some("application/json"),
+        }
+    }
+}
+
+/// A *raw* response from the Wistia API, which includes the status code
+/// and headers of the response, along with the JSON body.
+#[derive(Debug, Clone)]
+pub struct RawResponse {
+    /// The status code of the response.
+    pub status: StatusCode,
+    /// The headers of the response.
+    pub headers: HeaderMap,
+    /// The JSON body of the response, or `None` if the body is empty, such
+    /// as for a `204 No Content` response.
+    pub body: Option<Value>,
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Aggregated tracking statistics for the whole account.
+///
+/// # Docs
+/// <https://wistia.com/support/developers/stats-api#account>
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct AccountStats {
+    /// The total number of times that all videos have been loaded.
+    pub load_count: u64,
+    /// The total number of times that all videos have been played.
+    pub play_count: u64,
+    /// The total amount of time spent watching all videos.
+    pub hours_watched: f64,
+    /// Fields not (yet) modeled; see [unknown fields](crate::models#unknown-fields).
+    #[cfg_attr(not(feature = "strict"), serde(flatten))]
+    #[cfg_attr(feature = "strict", serde(skip))]
+    pub extra: Map<String, Value>,
+}
+
+/// Aggregated tracking statistics for a project.
+///
+/// # Docs
+/// <https://wistia.com/support/developers/stats-api#projects>
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ProjectStats {
+    /// The total number of times that the videos in the project have been loaded.
+    pub load_count: u64,
+    /// The total number of times that the videos in the project have been played.
+    pub play_count: u64,
+    /// The total amount of time spent watching the videos in the project.
+    pub hours_watched: f64,
+    /// The number of videos in the project.
+    pub number_of_videos: u64,
+    /// Fields not (yet) modeled; see [unknown fields](crate::models#unknown-fields).
+    #[cfg_attr(not(feature = "strict"), serde(flatten))]
+    #[cfg_attr(feature = "strict", serde(skip))]
+    pub extra: Map<String, Value>,
+}
+
+/// Aggregated tracking statistics for a media (typically a video).
+///
+/// # Docs
+/// <https://wistia.com/support/developers/stats-api#medias>
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct MediaStats {
+    /// The total number of times that the video has been loaded.
+    pub load_count: u64,
+    /// The total number of times that the video has been played.
+    pub play_count: u64,
+    /// The percentage of loads that resulted in a play, from `0.0` to `1.0`.
+    pub play_rate: f64,
+    /// The total amount of time spent watching the video.
+    pub hours_watched: f64,
+    /// The average percentage of the video that was watched, from `0.0` to `1.0`.
+    pub engagement: f64,
+    /// The number of unique visitors to the video.
+    pub visitors: u64,
+    /// Fields not (yet) modeled; see [unknown fields](crate::models#unknown-fields).
+    #[cfg_attr(not(feature = "strict"), serde(flatten))]
+    #[cfg_attr(feature = "strict", serde(skip))]
+    pub extra: Map<String, Value>,
+}
@@ -0,0 +1,366 @@
+//! Helpers for the timestamps and durations on the models.
+//!
+//! By default, timestamps such as `created` and `updated` are kept as the
+//! (RFC 3339) strings returned by Wistia, and durations as seconds in an
+//! `f64`. With the optional `chrono` (or `time`) feature, timestamps are
+//! instead de-serialized into a [`Timestamp`] of that crate, and durations
+//! into a [`Duration`]; if both features are enabled, `chrono` is used.
+//!
+//! Either way, the models offer `created_at()`, `updated_at()` and
+//! `duration()` accessors, which return [`SystemTime`] and [`Duration`]
+//! values that can be compared directly.
+//!
+use std::time::SystemTime;
+
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+use std::time::Duration;
+
+/// The type of the timestamps on the models, which is a `String` unless the
+/// `chrono` or `time` feature is enabled.
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+pub type Timestamp = String;
+
+/// The type of the timestamps on the models, which is a `String` unless the
+/// `chrono` or `time` feature is enabled.
+#[cfg(feature = "chrono")]
+pub type Timestamp = chrono::DateTime<chrono::FixedOffset>;
+
+/// The type of the timestamps on the models, which is a `String` unless the
+/// `chrono` or `time` feature is enabled.
+#[cfg(all(feature = "time", not(feature = "chrono")))]
+pub type Timestamp = time::OffsetDateTime;
+
+/// Returns the timestamp of a model built via `Default`.
+#[cfg(not(all(feature = "time", not(feature = "chrono"))))]
+pub(crate) fn default_timestamp() -> Timestamp {
+    Timestamp::default()
+}
+
+/// Returns the timestamp of a model built via `Default`, which is the Unix
+/// epoch as `time` has no default datetime.
+#[cfg(all(feature = "time", not(feature = "chrono")))]
+pub(crate) fn default_timestamp() -> Timestamp {
+    Timestamp::UNIX_EPOCH
+}
+
+/// Convert a timestamp into a `SystemTime`, returning `None` if it could
+/// not be parsed.
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+pub(crate) fn to_system_time(ts: &Timestamp) -> Option<SystemTime> {
+    parse_rfc3339(ts)
+}
+
+/// Convert a timestamp into a `SystemTime`.
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub(crate) fn to_system_time(ts: &Timestamp) -> Option<SystemTime> {
+    Some((*ts).into())
+}
+
+/// Convert a duration in seconds into a `Duration`, returning `None` if it
+/// is negative or not a finite number.
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+pub(crate) fn to_duration(secs: f64) -> Option<Duration> {
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+/// (De)serialize a [`Timestamp`] as an RFC 3339 string, such as
+/// `2012-10-26T16:47:09+00:00`.
+pub(crate) mod rfc3339 {
+    use super::Timestamp;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[cfg(not(any(feature = "chrono", feature = "time")))]
+    pub(crate) fn serialize<S: Serializer>(
+        ts: &Timestamp,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        ts.serialize(serializer)
+    }
+
+    #[cfg(not(any(feature = "chrono", feature = "time")))]
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Timestamp, D::Error> {
+        Timestamp::deserialize(deserializer)
+    }
+
+    #[cfg(feature = "chrono")]
+    pub(crate) fn serialize<S: Serializer>(
+        ts: &Timestamp,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        ts.to_rfc3339().serialize(serializer)
+    }
+
+    #[cfg(feature = "chrono")]
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Timestamp, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Timestamp::parse_from_rfc3339(&s).map_err(serde::de::Error::custom)
+    }
+
+    #[cfg(all(feature = "time", not(feature = "chrono")))]
+    pub(crate) fn serialize<S: Serializer>(
+        ts: &Timestamp,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        use time::format_description::well_known::Rfc3339;
+
+        ts.format(&Rfc3339)
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    #[cfg(all(feature = "time", not(feature = "chrono")))]
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Timestamp, D::Error> {
+        use time::format_description::well_known::Rfc3339;
+
+        let s = String::deserialize(deserializer)?;
+        Timestamp::parse(&s, &Rfc3339).map_err(serde::de::Error::custom)
+    }
+}
+
+/// (De)serialize a `Duration` as a number of seconds, for the `chrono` and
+/// `time` features.
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub(crate) mod seconds {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(d: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        d.as_secs_f64().serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Duration, D::Error> {
+        let secs = f64::deserialize(deserializer)?;
+        Duration::try_from_secs_f64(secs).map_err(serde::de::Error::custom)
+    }
+
+    /// (De)serialize an optional `Duration`, where `null` is `None`.
+    pub(crate) mod option {
+        use std::time::Duration;
+
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub(crate) fn serialize<S: Serializer>(
+            d: &Option<Duration>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match d {
+                Some(d) => super::serialize(d, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Duration>, D::Error> {
+            match Option::<f64>::deserialize(deserializer)? {
+                Some(secs) => Duration::try_from_secs_f64(secs)
+                    .map(Some)
+                    .map_err(serde::de::Error::custom),
+                None => Ok(None),
+            }
+        }
+    }
+}
+
+/// Parse an RFC 3339 timestamp, such as `2012-10-26T16:47:09+00:00`.
+///
+/// This is only used when neither the `chrono` nor the `time` feature is
+/// enabled, so as not to pull in a datetime crate by default.
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+fn parse_rfc3339(s: &str) -> Option<SystemTime> {
+    let b = s.as_bytes();
+    let num = |start: usize, len: usize| -> Option<i64> {
+        let digits = s.get(start..start + len)?;
+        if !digits.bytes().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse().ok()
+    };
+
+    if b.len() < 20
+        || b[4] != b'-'
+        || b[7] != b'-'
+        || !matches!(b[10], b'T' | b't' | b' ')
+        || b[13] != b':'
+        || b[16] != b':'
+    {
+        return None;
+    }
+
+    let (year, month, day) = (num(0, 4)?, num(5, 2)?, num(8, 2)?);
+    let (hour, min, sec) = (num(11, 2)?, num(14, 2)?, num(17, 2)?);
+
+    let is_leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        2 if is_leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None,
+    };
+
+    if !(1..=days_in_month).contains(&day) || hour > 23 || min > 59 || sec > 60 {
+        return None;
+    }
+
+    // Fractional seconds
+    let mut rest = &s[19..];
+    let mut nanos = 0;
+    if let Some(frac) = rest.strip_prefix('.') {
+        let end = frac
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(frac.len());
+        if end == 0 {
+            return None;
+        }
+        for (i, c) in frac[..end].bytes().take(9).enumerate() {
+            nanos += u32::from(c - b'0') * 10u32.pow(8 - i as u32);
+        }
+        rest = &frac[end..];
+    }
+
+    // Offset from UTC
+    let offset = match rest.as_bytes() {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), _, _, b':', _, _] => {
+            let (hours, mins) = (num(s.len() - 5, 2)?, num(s.len() - 2, 2)?);
+            if hours > 23 || mins > 59 {
+                return None;
+            }
+            let offset = hours * 3600 + mins * 60;
+            if *sign == b'-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => return None,
+    };
+
+    // Days since the epoch, adapted from Howard Hinnant's `days_from_civil`.
+    // See: <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    let secs = days * 86_400 + hour * 3600 + min * 60 + sec - offset;
+
+    if secs >= 0 {
+        SystemTime::UNIX_EPOCH.checked_add(Duration::new(secs as u64, nanos))
+    } else {
+        SystemTime::UNIX_EPOCH
+            .checked_sub(Duration::from_secs(secs.unsigned_abs()))?
+            .checked_add(Duration::from_nanos(nanos.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use serde::Deserialize;
+    use serde_json::{from_value, json};
+
+    #[derive(Debug, Deserialize)]
+    struct Model {
+        #[serde(with = "rfc3339")]
+        created: Timestamp,
+    }
+
+    fn system_time(ts: &str) -> Option<SystemTime> {
+        let model: Model = from_value(json!({ "created": ts })).ok()?;
+        to_system_time(&model.created)
+    }
+
+    #[test]
+    fn test_to_system_time() {
+        let time = system_time("2012-10-26T16:47:09+00:00").unwrap();
+        assert_eq!(
+            time,
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_351_270_029)
+        );
+
+        let time = system_time("2012-10-26T12:47:09.5-04:00").unwrap();
+        assert_eq!(
+            time,
+            SystemTime::UNIX_EPOCH + Duration::from_millis(1_351_270_029_500)
+        );
+
+        let time = system_time("1969-12-31T23:59:59Z").unwrap();
+        assert_eq!(time, SystemTime::UNIX_EPOCH - Duration::from_secs(1));
+
+        let time = system_time("2024-02-29T00:00:00Z").unwrap();
+        assert_eq!(
+            time,
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_709_164_800)
+        );
+    }
+
+    #[test]
+    fn test_invalid_timestamps() {
+        for s in [
+            "",
+            "2012-10-26",
+            "2012-13-26T16:47:09Z",
+            "2023-02-29T00:00:00Z",
+            "2023-02-31T00:00:00Z",
+            "2023-04-31T00:00:00Z",
+            "2012-10-26T24:00:00Z",
+            "2012-10-26T16:47:09",
+            "2012-10-26T16:47:09+0000",
+            "2012-10-26T16:47:09+24:00",
+            "2012-10-26T16:47:09.Z",
+        ] {
+            assert_eq!(system_time(s), None, "{s}");
+        }
+    }
+
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    #[test]
+    fn test_invalid_timestamps_fail_deserialization() {
+        let err = from_value::<Model>(json!({"created": "2023-02-31T00:00:00Z"})).unwrap_err();
+
+        assert!(!err.to_string().is_empty());
+    }
+
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    #[test]
+    fn test_seconds() {
+        #[derive(Debug, Deserialize)]
+        struct Model {
+            #[serde(with = "seconds::option")]
+            duration: Option<Duration>,
+        }
+
+        let model: Model = from_value(json!({"duration": 1.5})).unwrap();
+        assert_eq!(model.duration, Some(Duration::from_millis(1500)));
+
+        let model: Model = from_value(json!({"duration": null})).unwrap();
+        assert_eq!(model.duration, None);
+
+        assert!(from_value::<Model>(json!({"duration": -1.0})).is_err());
+    }
+
+    #[cfg(not(any(feature = "chrono", feature = "time")))]
+    #[test]
+    fn test_to_duration() {
+        assert_eq!(to_duration(1.5), Some(Duration::from_millis(1500)));
+        assert_eq!(to_duration(-1.0), None);
+        assert_eq!(to_duration(f64::NAN), None);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::models::ProjectHashedId;
+
 use serde::Serialize;
 
 use std::path::Path;
@@ -20,7 +22,12 @@ pub struct UploadRequest<'a> {
     /// **Required**. A 64 character hex string. This parameter can be found
     /// on your [API access page].
     ///
+    /// This is omitted if empty, such as when the token is instead sent in
+    /// the `Authorization` header; see [`UploadAuth`].
+    ///
     /// [API access page]: https://wistia.com/support/developers/data-api#getting-started
+    /// [`UploadAuth`]: crate::UploadAuth
+    #[serde(skip_serializing_if = "str::is_empty")]
     pub access_token: &'a str,
     /// **Required** unless `file` is specified. The web location of the media
     /// file to import.
@@ -40,6 +47,7 @@ pub struct UploadRequest<'a> {
     pub contact_id: Option<&'a str>,
 }
 
+#[cfg(feature = "upload-url")]
 #[derive(Default, Clone)]
 pub(crate) struct UploadUrlRequest<'a> {
     /// **Required**. The web location of the media file to import.
@@ -47,7 +55,7 @@ pub(crate) struct UploadUrlRequest<'a> {
     /// The hashed id of the project to upload media into. If omitted, a new
     /// project will be created and uploaded to. The naming convention used
     /// for such projects is `Uploads_YYYY-MM-DD`.
-    pub project_id: Option<&'a str>,
+    pub project_id: Option<ProjectHashedId>,
     /// A display name to use for the media in Wistia. If omitted, the filename
     /// will be used instead. This field is limited to 255 characters.
     pub name: Option<&'a str>,
@@ -70,7 +78,7 @@ pub(crate) struct UploadFileRequest<'a, P: AsRef<Path>> {
     /// The hashed id of the project to upload media into. If omitted, a new
     /// project will be created and uploaded to. The naming convention used
     /// for such projects is `Uploads_YYYY-MM-DD`.
-    pub project_id: Option<&'a str>,
+    pub project_id: Option<ProjectHashedId>,
     /// A display name to use for the media in Wistia. If omitted, the filename
     /// will be used instead. This field is limited to 255 characters.
     pub name: Option<&'a str>,
@@ -105,7 +113,7 @@ pub(crate) struct UploadStreamRequest<'a> {
     /// The hashed id of the project to upload media into. If omitted, a new
     /// project will be created and uploaded to. The naming convention used
     /// for such projects is `Uploads_YYYY-MM-DD`.
-    pub project_id: Option<&'a str>,
+    pub project_id: Option<ProjectHashedId>,
     /// A display name to use for the media in Wistia. If omitted, the filename
     /// will be used instead. This field is limited to 255 characters.
     pub name: Option<&'a str>,
@@ -1,27 +1,75 @@
-use crate::models::{MediaStatus, MediaType, Thumbnail};
+#[cfg(any(feature = "chrono", feature = "time"))]
+use crate::models::timestamp::seconds;
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+use crate::models::timestamp::to_duration;
+use crate::models::timestamp::{rfc3339, to_system_time, Timestamp};
+use crate::models::{AccountId, MediaHashedId, MediaId, MediaStatus, MediaType, Thumbnail};
+
+use std::time::{Duration, SystemTime};
 
 use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct UploadResponse {
-    pub id: u64,
+    pub id: MediaId,
     pub name: String,
     #[serde(rename = "type")]
     pub media_type: MediaType,
-    pub created: String,
-    pub updated: String,
+    #[serde(with = "rfc3339")]
+    pub created: Timestamp,
+    #[serde(with = "rfc3339")]
+    pub updated: Timestamp,
     /// Note: this field will not be populated when uploading URL links
+    #[cfg(not(any(feature = "chrono", feature = "time")))]
     #[serde(default)]
     pub duration: f64,
-    pub hashed_id: String,
+    /// Note: this field will not be populated when uploading URL links
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    #[serde(default, with = "seconds")]
+    pub duration: Duration,
+    pub hashed_id: MediaHashedId,
     #[serde(deserialize_with = "empty_string_is_none")]
     pub description: Option<String>,
     pub progress: f32,
     #[serde(default)]
     pub status: MediaStatus,
     pub thumbnail: Thumbnail,
-    pub account_id: u64,
+    pub account_id: AccountId,
     pub archived: bool,
+    /// Fields not (yet) modeled; see [unknown fields](crate::models#unknown-fields).
+    #[cfg_attr(not(feature = "strict"), serde(flatten))]
+    #[cfg_attr(feature = "strict", serde(skip))]
+    pub extra: Map<String, Value>,
+}
+
+impl UploadResponse {
+    /// Returns when the uploaded media was created, or `None` if the
+    /// timestamp could not be parsed.
+    pub fn created_at(&self) -> Option<SystemTime> {
+        to_system_time(&self.created)
+    }
+
+    /// Returns when the uploaded media was last updated, such as once it
+    /// is processed, or `None` if the timestamp could not be parsed.
+    pub fn updated_at(&self) -> Option<SystemTime> {
+        to_system_time(&self.updated)
+    }
+
+    /// Returns the length of the media, or `None` if it is not populated,
+    /// as is the case when uploading URL links.
+    #[cfg(not(any(feature = "chrono", feature = "time")))]
+    pub fn duration(&self) -> Option<Duration> {
+        to_duration(self.duration).filter(|d| !d.is_zero())
+    }
+
+    /// Returns the length of the media, or `None` if it is not populated,
+    /// as is the case when uploading URL links.
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    pub fn duration(&self) -> Option<Duration> {
+        Some(self.duration).filter(|d| !d.is_zero())
+    }
 }
 
 fn empty_string_is_none<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
@@ -39,38 +87,18 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::fixtures::{with_fields, UPLOAD_JSON};
 
     use crate::log::debug;
-    use serde_json::from_str;
+    use serde_json::{from_str, from_value, json, Value};
+    use std::time::SystemTime;
 
     /// Using the provided [Example Response][]
     ///
     /// [Example Response]: https://wistia.com/support/developers/upload-api#example-response
     #[test]
     fn test_deserialize_upload_response_works() {
-        let resp = r#"
-        {
-          "id": 2208087,
-          "account_id": 123456789,
-          "name": "dramatic_squirrel.mp4",
-          "description": "",
-          "type": "Video",
-          "archived": false,
-          "created": "2012-10-26T16:47:09+00:00",
-          "updated": "2012-10-26T16:47:10+00:00",
-          "duration": 5.333000183105469,
-          "hashed_id": "gn69c10tqw",
-          "progress": 0.0,
-          "thumbnail":
-          {
-            "url": "http://embed.wistia.com/deliveries/ffbada01610466e66f67a5dbbf473ed6574a6405.jpg?image_crop_resized=100x60",
-            "width": 100,
-            "height": 60
-          }
-        }
-        "#;
-
-        let resp: UploadResponse = from_str(resp).unwrap();
+        let resp: UploadResponse = from_str(UPLOAD_JSON).unwrap();
 
         debug!("{:#?}", resp);
 
@@ -79,4 +107,100 @@ mod tests {
         assert_eq!(resp.status, MediaStatus::Queued);
         assert_eq!(resp.media_type, MediaType::Video);
     }
+
+    #[test]
+    fn test_timestamps_and_duration() {
+        let resp: UploadResponse = from_str(UPLOAD_JSON).unwrap();
+
+        assert_eq!(
+            resp.created_at(),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_351_270_029))
+        );
+        assert!(resp.updated_at() > resp.created_at());
+        assert_eq!(
+            resp.duration(),
+            Some(Duration::from_secs_f64(5.333000183105469))
+        );
+
+        #[cfg(not(any(feature = "chrono", feature = "time")))]
+        assert_eq!(resp.created, "2012-10-26T16:47:09+00:00");
+
+        #[cfg(feature = "chrono")]
+        assert_eq!(resp.created.timestamp(), 1_351_270_029);
+
+        #[cfg(all(feature = "time", not(feature = "chrono")))]
+        assert_eq!(resp.updated.unix_timestamp(), 1_351_270_030);
+    }
+
+    #[test]
+    fn test_timestamps_round_trip() {
+        let resp: UploadResponse = from_str(UPLOAD_JSON).unwrap();
+        let value = serde_json::to_value(&resp).unwrap();
+
+        let duration = value["duration"].as_f64().unwrap();
+        assert!((duration - 5.333).abs() < 1e-6);
+
+        #[cfg(not(all(feature = "time", not(feature = "chrono"))))]
+        assert_eq!(value["created"], "2012-10-26T16:47:09+00:00");
+
+        // `time` formats the UTC offset as `Z`
+        #[cfg(all(feature = "time", not(feature = "chrono")))]
+        assert_eq!(value["created"], "2012-10-26T16:47:09Z");
+    }
+
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    #[test]
+    fn test_invalid_timestamp_is_an_error() {
+        let resp = with_fields(UPLOAD_JSON, json!({"created": "2023-02-31T00:00:00+00:00"}));
+
+        assert!(from_value::<UploadResponse>(resp).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_upload_response_with_unknown_values() {
+        let resp = with_fields(
+            UPLOAD_JSON,
+            json!({"type": "Model3D", "status": "scanning", "duration": null}),
+        );
+
+        let resp: UploadResponse = from_value(resp).unwrap();
+
+        assert_eq!(resp.media_type, MediaType::Unknown("Model3D".into()));
+        assert_eq!(resp.status, MediaStatus::Unknown("scanning".into()));
+
+        let value = serde_json::to_value(&resp).unwrap();
+
+        assert_eq!(value["type"], "Model3D");
+        assert_eq!(value["status"], "scanning");
+    }
+
+    fn response_with_extra_fields() -> Value {
+        let mut resp = with_fields(UPLOAD_JSON, json!({"tags": ["squirrel", "drama"]}));
+        resp["thumbnail"]["aspect_ratio"] = json!(1.67);
+
+        resp
+    }
+
+    #[cfg(not(feature = "strict"))]
+    #[test]
+    fn test_unknown_fields_are_preserved() {
+        let resp: UploadResponse = from_value(response_with_extra_fields()).unwrap();
+
+        assert_eq!(resp.extra["tags"], json!(["squirrel", "drama"]));
+        assert_eq!(resp.thumbnail.extra["aspect_ratio"], 1.67);
+
+        let value = serde_json::to_value(&resp).unwrap();
+
+        assert_eq!(value["tags"][1], "drama");
+        assert_eq!(value["thumbnail"]["aspect_ratio"], 1.67);
+        assert!(value.get("extra").is_none());
+    }
+
+    #[cfg(feature = "strict")]
+    #[test]
+    fn test_unknown_fields_are_denied() {
+        let err = from_value::<UploadResponse>(response_with_extra_fields()).unwrap_err();
+
+        assert!(err.to_string().contains("unknown field"));
+    }
 }
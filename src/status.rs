@@ -1,14 +1,16 @@
 //! Utilities to validate a response to ensure that its *status code*
 //! indicates that it is a success.
 //!
+use crate::api::retry_after;
 use crate::log::error;
 use crate::types::Result;
-use crate::utils::{host_with_path, resp_to_string};
-use crate::{RustWistiaError, WistiaError};
+use crate::utils::{host_with_path, redact, redact_url, url_secrets};
+use crate::{RequestContext, RustWistiaError, WistiaError};
 
 use core::result::Result::{Err, Ok};
 
-use http::Response;
+use http::{Method, Response};
+use hyper::body::{to_bytes, Bytes};
 use hyper::{http, Body};
 
 /// Check the `status` of a Response and raise an error (RequestError) if the
@@ -22,11 +24,40 @@ use hyper::{http, Body};
 /// the status code, is between 200 and 400, this will return `Ok()`. This
 /// is **not** a check to see if the response code is ``200 OK``.
 ///
+/// The error includes the (redacted) URL of the request, along with the
+/// headers and raw body of the response. Any access token in the
+/// `request_url` is redacted from the error, along with the response body
+/// if it happens to echo the token. As the method of the request is not
+/// known, it is not set in the error; use [`raise_for_status_with_method`]
+/// to set it.
+///
 /// # Credits
 ///
 /// <https://github.com/psf/requests/blob/95f456733656ed93645ff0250bfa54f6d256f6fe/requests/models.py#L945>
 ///
 pub async fn raise_for_status(request_url: &str, resp: &mut Response<Body>) -> Result<()> {
+    check_status(None, request_url, resp, &[]).await
+}
+
+/// Check the `status` of a Response as with [`raise_for_status`], and
+/// include the `method` of the request in the error.
+pub async fn raise_for_status_with_method(
+    method: &Method,
+    request_url: &str,
+    resp: &mut Response<Body>,
+) -> Result<()> {
+    check_status(Some(method), request_url, resp, &[]).await
+}
+
+/// Check the `status` of a Response as with [`raise_for_status`], and also
+/// redact each of `secrets` (such as an access token which is sent in a
+/// header) from the error.
+pub(crate) async fn check_status(
+    method: Option<&Method>,
+    request_url: &str,
+    resp: &mut Response<Body>,
+    secrets: &[&str],
+) -> Result<()> {
     let status_code = resp.status().as_u16();
     let reason: String;
 
@@ -53,7 +84,20 @@ pub async fn raise_for_status(request_url: &str, resp: &mut Response<Body>) -> R
         return Ok(());
     }
 
-    let resp_data = resp_to_string(resp).await?;
+    let mut secrets = secrets.to_vec();
+    secrets.extend(url_secrets(request_url));
+
+    // The secrets are redacted before the response is parsed; as the
+    // placeholder is plain text, a JSON response is still valid. The raw
+    // body is kept as-is, unless it contains a secret.
+    let body = to_bytes(resp.body_mut()).await?;
+    let text = String::from_utf8_lossy(&body);
+    let resp_data = redact(&text, &secrets);
+    let body = if resp_data == text {
+        body
+    } else {
+        Bytes::from(resp_data.clone())
+    };
 
     // Attempt to de-serialize the response data into a `WistiaError`
     // object, and set the `error` field. If there are any errors in
@@ -67,13 +111,53 @@ pub async fn raise_for_status(request_url: &str, resp: &mut Response<Body>) -> R
         }
     };
 
-    let e = RustWistiaError::Request {
+    // Only log a summary, as the response body can be large.
+    error!(
+        "{method}request failed: {reason} (request id: {request_id})",
+        method = method.map(|m| format!("{m} ")).unwrap_or_default(),
+        reason = reason,
+        request_id = resp
+            .headers()
+            .get("x-request-id")
+            .and_then(|id| id.to_str().ok())
+            .unwrap_or("none"),
+    );
+
+    Err(RustWistiaError::Request {
         status_code,
         reason,
-        error,
-    };
+        error: Box::new(error),
+        retry_after: retry_after(resp.headers()),
+        context: Box::new(RequestContext {
+            method: method.cloned(),
+            url: redact_url(request_url),
+            headers: resp.headers().clone(),
+            body,
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    error!("{:#?}", e);
+    fn not_found() -> Response<Body> {
+        Response::builder()
+            .status(404)
+            .body(Body::from(r#"{"error": "not found"}"#))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_raise_for_status_without_method() {
+        let url = "https://api.wistia.com/v1/medias/abc1234567.json";
 
-    Err(e)
+        let err = raise_for_status(url, &mut not_found()).await.unwrap_err();
+        assert_eq!(err.context().unwrap().method, None);
+
+        let err = raise_for_status_with_method(&Method::DELETE, url, &mut not_found())
+            .await
+            .unwrap_err();
+        assert_eq!(err.context().unwrap().method, Some(Method::DELETE));
+    }
 }
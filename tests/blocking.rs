@@ -0,0 +1,99 @@
+#![cfg(feature = "blocking")]
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{MEDIA_JSON, UPLOAD_JSON};
+use rust_wistia::blocking::{DataClient, UrlUploader};
+use rust_wistia::mock::{Mock, MockTransport};
+use rust_wistia::{ClientBuilder, RetryPolicy, RustWistiaError, UploadClient};
+
+#[test]
+fn test_blocking_data_client() {
+    let transport = MockTransport::new();
+    transport
+        .mock(Mock::get("/v1/medias/abc1234567.json").body(MEDIA_JSON))
+        .mock(Mock::get("/v1/medias/missing1234.json").status(404));
+
+    let client = DataClient::with_transport("my-token", transport.clone());
+
+    let media = client.get_media("abc1234567").unwrap();
+    assert_eq!(media.hashed_id, "abc1234567");
+
+    let err = client.get_media("missing1234").unwrap_err();
+    assert!(matches!(
+        err,
+        RustWistiaError::Request {
+            status_code: 404,
+            ..
+        }
+    ));
+
+    transport.assert_called_times("GET", "/v1/medias/abc1234567.json", 1);
+}
+
+#[test]
+fn test_blocking_url_uploader() {
+    let transport = MockTransport::new();
+    transport.mock(Mock::post("/").body(UPLOAD_JSON));
+
+    let client = UploadClient::with_transport("my-token", transport.clone());
+    let res = UrlUploader::with_client("https://example.com/my/video.mp4", client)
+        .name("My Video")
+        .send()
+        .unwrap();
+
+    assert_eq!(res.hashed_id, "gn69c10tqw");
+    transport.assert_called_times("POST", "/", 1);
+}
+
+#[test]
+fn test_blocking_over_http() {
+    // The server runs on its own runtime, as the client blocks this thread
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let (base_url, server) = rt.block_on(common::serve([("200 OK", MEDIA_JSON)]));
+
+    let client: DataClient = ClientBuilder::new()
+        .access_token("my-token")
+        .base_url(&base_url)
+        .build_data()
+        .unwrap()
+        .into();
+
+    let media = client.get_media("abc1234567").unwrap();
+    assert_eq!(media.name, "My Video");
+
+    let requests = rt.block_on(server).unwrap();
+    assert!(requests[0].starts_with("GET /medias/abc1234567.json"));
+}
+
+#[test]
+fn test_blocking_calls_run_concurrently() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let base_url = rt.block_on(common::serve_hanging());
+
+    let timeout = Duration::from_millis(300);
+    let client: DataClient = ClientBuilder::new()
+        .access_token("my-token")
+        .base_url(&base_url)
+        .read_timeout(timeout)
+        .retry_policy(RetryPolicy::none())
+        .build_data()
+        .unwrap()
+        .into();
+
+    // Each call waits out the timeout; if they ran one at a time, the pair
+    // would take at least twice as long
+    let start = Instant::now();
+    std::thread::scope(|s| {
+        let calls: Vec<_> = (0..2)
+            .map(|_| s.spawn(|| client.get_media("abc1234567")))
+            .collect();
+        for call in calls {
+            let err = call.join().unwrap().unwrap_err();
+            assert!(matches!(err, RustWistiaError::Timeout(_)));
+        }
+    });
+    assert!(start.elapsed() < timeout * 2);
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::{serve, serve_hanging, MEDIA_JSON};
+use rust_wistia::{ClientBuilder, HttpVersion, RetryPolicy, RustWistiaError};
+
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn test_user_agent_and_default_headers() {
+    let (base_url, server) = serve(vec![("200 OK", MEDIA_JSON)]).await;
+
+    let client = ClientBuilder::new()
+        .access_token("my-token")
+        .base_url(&base_url)
+        .user_agent("my-app/1.0")
+        .default_header("X-Request-Source", "tests")
+        .http_version(HttpVersion::Http1)
+        .pool_max_idle_per_host(1)
+        .build_data()
+        .unwrap();
+
+    client.get_media("abc1234567").await.unwrap();
+
+    let requests = server.await.unwrap();
+    assert!(requests[0].contains("user-agent: my-app/1.0\r\n"));
+    assert!(requests[0].contains("x-request-source: tests\r\n"));
+    assert!(requests[0].contains("authorization: Bearer my-token\r\n"));
+}
+
+#[tokio::test]
+async fn test_default_user_agent() {
+    let (base_url, server) = serve(vec![("200 OK", MEDIA_JSON)]).await;
+
+    let client = ClientBuilder::new()
+        .access_token("my-token")
+        .base_url(&base_url)
+        .build_data()
+        .unwrap();
+
+    client.get_media("abc1234567").await.unwrap();
+
+    let requests = server.await.unwrap();
+    assert!(requests[0].contains(&format!(
+        "user-agent: rust-wistia/{}\r\n",
+        env!("CARGO_PKG_VERSION")
+    )));
+}
+
+#[tokio::test]
+async fn test_total_timeout() {
+    let base_url = serve_hanging().await;
+
+    let client = ClientBuilder::new()
+        .access_token("my-token")
+        .base_url(&base_url)
+        .timeout(Duration::from_millis(100))
+        .retry_policy(RetryPolicy::none())
+        .build_data()
+        .unwrap();
+
+    let start = Instant::now();
+    let err = client.get_media("abc1234567").await.unwrap_err();
+
+    assert!(matches!(err, RustWistiaError::Timeout(t) if t == Duration::from_millis(100)));
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[cfg(feature = "upload-url")]
+#[tokio::test]
+async fn test_read_timeout() {
+    let base_url = serve_hanging().await;
+
+    let client = ClientBuilder::new()
+        .access_token("my-token")
+        .upload_url(&base_url)
+        .read_timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+
+    let err = client
+        .url_uploader("https://example.com/my/video.mp4")
+        .send()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, RustWistiaError::Timeout(_)));
+}
@@ -0,0 +1,266 @@
+//! Shared helpers for the integration tests.
+#![allow(dead_code)]
+
+use tokio::io::{copy_bidirectional, AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// A sample response from the `Medias#show` endpoint.
+pub const MEDIA_JSON: &str = r#"
+{
+  "hashed_id": "abc1234567",
+  "id": 4489021,
+  "name": "My Video",
+  "type": "Video",
+  "created": "2013-09-19T15:30:49+00:00",
+  "updated": "2013-10-28T20:53:16+00:00",
+  "duration": 167.0,
+  "status": "ready",
+  "description": "",
+  "progress": 1.0,
+  "thumbnail": {
+    "url": "http://embed.wistia.com/deliveries/7fbf9c2fe9c6585f9aa032f43f0aecc3f287e86b.jpg?image_crop_resized=100x60",
+    "width": 100,
+    "height": 60
+  },
+  "project": {
+    "hashed_id": "xyz1234567",
+    "id": 464427,
+    "name": "My Project"
+  },
+  "assets": [],
+  "section": null
+}
+"#;
+
+/// A sample response from the Upload API.
+pub const UPLOAD_JSON: &str = r#"
+{
+  "id": 2208087,
+  "account_id": 123456789,
+  "name": "dramatic_squirrel.mp4",
+  "description": "",
+  "type": "Video",
+  "archived": false,
+  "created": "2012-10-26T16:47:09+00:00",
+  "updated": "2012-10-26T16:47:10+00:00",
+  "duration": 5.333000183105469,
+  "hashed_id": "gn69c10tqw",
+  "progress": 0.0,
+  "thumbnail": {
+    "url": "http://embed.wistia.com/deliveries/ffbada01610466e66f67a5dbbf473ed6574a6405.jpg?image_crop_resized=100x60",
+    "width": 100,
+    "height": 60
+  }
+}
+"#;
+
+/// A sample response from the `Stats:Medias#show` endpoint.
+pub const MEDIA_STATS_JSON: &str = r#"
+{
+  "load_count": 3,
+  "play_count": 2,
+  "play_rate": 0.6666666666666666,
+  "hours_watched": 0.02,
+  "engagement": 0.51,
+  "visitors": 2
+}
+"#;
+
+/// A response for the local HTTP server to reply with.
+#[derive(Clone, Debug)]
+pub struct Reply {
+    pub status: &'static str,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: &'static str,
+}
+
+impl Reply {
+    pub fn new(status: &'static str, body: &'static str) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body,
+        }
+    }
+
+    pub fn header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    /// Format the response, with a `Connection: close` header if `close`
+    /// is set.
+    pub fn to_http(&self, close: bool) -> String {
+        let mut resp = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n",
+            self.status,
+            self.body.len()
+        );
+        for (name, value) in &self.headers {
+            resp.push_str(&format!("{name}: {value}\r\n"));
+        }
+        if close {
+            resp.push_str("Connection: close\r\n");
+        }
+        resp.push_str("\r\n");
+        resp.push_str(self.body);
+        resp
+    }
+}
+
+impl From<(&'static str, &'static str)> for Reply {
+    fn from((status, body): (&'static str, &'static str)) -> Self {
+        Self::new(status, body)
+    }
+}
+
+/// Start a local HTTP server which replies to each incoming request with
+/// the next of `responses`, such as a tuple of `(status line, body)`.
+///
+/// Returns the base URL of the server, and a handle which resolves to the
+/// raw requests that were received.
+pub async fn serve(
+    responses: impl IntoIterator<Item = impl Into<Reply>>,
+) -> (String, JoinHandle<Vec<String>>) {
+    let responses: Vec<Reply> = responses.into_iter().map(Into::into).collect();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = tokio::spawn(async move {
+        let mut requests = Vec::new();
+
+        for reply in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            requests.push(read_request(&mut socket).await);
+
+            let resp = reply.to_http(true);
+            socket.write_all(resp.as_bytes()).await.unwrap();
+            socket.shutdown().await.ok();
+        }
+
+        requests
+    });
+
+    (base_url, handle)
+}
+
+/// Start a local HTTP server which accepts a *single* connection, and
+/// keeps it alive to reply to each incoming request with the next of
+/// `responses`, such as a tuple of `(status line, body)`.
+///
+/// This can be used to check that requests share a connection pool, since
+/// any request sent on a new connection never receives a response.
+pub async fn serve_keep_alive(
+    responses: impl IntoIterator<Item = impl Into<Reply>>,
+) -> (String, JoinHandle<Vec<String>>) {
+    let responses: Vec<Reply> = responses.into_iter().map(Into::into).collect();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        let (mut socket, _) = listener.accept().await.unwrap();
+
+        for reply in responses {
+            requests.push(read_request(&mut socket).await);
+
+            let resp = reply.to_http(false);
+            socket.write_all(resp.as_bytes()).await.unwrap();
+        }
+
+        requests
+    });
+
+    (base_url, handle)
+}
+
+/// Start a local HTTP server which reads each incoming request, but never
+/// sends a response.
+pub async fn serve_hanging() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                read_request(&mut socket).await;
+                std::future::pending::<()>().await;
+            });
+        }
+    });
+
+    base_url
+}
+
+/// Start a local HTTP proxy which accepts a single `CONNECT` request, and
+/// tunnels the connection to the requested host.
+///
+/// If `auth` is set, such as `Basic dXNlcjpwYXNz`, a request without this
+/// `Proxy-Authorization` header is refused with a `407`. Returns the URL of
+/// the proxy, and a handle which resolves to the raw `CONNECT` request.
+pub async fn serve_proxy(auth: Option<&'static str>) -> (String, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let proxy_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let req = read_request(&mut socket).await;
+
+        let authorized = auth.is_none_or(|auth| {
+            req.lines()
+                .any(|l| l.eq_ignore_ascii_case(&format!("proxy-authorization: {auth}")))
+        });
+        if !authorized {
+            socket
+                .write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n")
+                .await
+                .unwrap();
+            return req;
+        }
+
+        let target = req.split_whitespace().nth(1).unwrap().to_owned();
+        let mut upstream = TcpStream::connect(target).await.unwrap();
+
+        socket
+            .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+            .await
+            .unwrap();
+        copy_bidirectional(&mut socket, &mut upstream).await.ok();
+
+        req
+    });
+
+    (proxy_url, handle)
+}
+
+/// Read an HTTP/1.1 request (including any body) from a socket.
+pub async fn read_request<S: AsyncReadExt + Unpin>(socket: &mut S) -> String {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    loop {
+        let n = socket.read(&mut chunk).await.unwrap();
+        buf.extend_from_slice(&chunk[..n]);
+
+        let text = String::from_utf8_lossy(&buf);
+        if let Some(end) = text.find("\r\n\r\n") {
+            let content_length = text[..end]
+                .lines()
+                .find_map(|l| {
+                    let (k, v) = l.split_once(':')?;
+                    k.eq_ignore_ascii_case("content-length")
+                        .then(|| v.trim().parse().ok())?
+                })
+                .unwrap_or(0);
+
+            if buf.len() >= end + 4 + content_length {
+                return String::from_utf8_lossy(&buf).into_owned();
+            }
+        }
+
+        if n == 0 {
+            return String::from_utf8_lossy(&buf).into_owned();
+        }
+    }
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use common::{serve, Reply, MEDIA_JSON};
+use rust_wistia::models::UpdateMediaRequest;
+use rust_wistia::{DataClient, RustWistiaError};
+
+use std::sync::Arc;
+
+#[tokio::test]
+async fn test_get_media_with_base_url() {
+    let (base_url, server) = serve(vec![("200 OK", MEDIA_JSON)]).await;
+
+    let client = DataClient::from("my-token")
+        .with_base_url(&format!("{base_url}/v1"))
+        .unwrap();
+
+    let media = client.get_media("abc1234567").await.unwrap();
+    assert_eq!(media.hashed_id, "abc1234567");
+
+    let requests = server.await.unwrap();
+    assert!(requests[0].starts_with("GET /v1/medias/abc1234567.json HTTP/1.1\r\n"));
+    assert!(requests[0].contains("authorization: Bearer my-token\r\n"));
+}
+
+#[tokio::test]
+async fn test_error_response_from_base_url() {
+    let (base_url, server) =
+        serve(vec![("404 Not Found", r#"{"error": "Media not found"}"#)]).await;
+
+    let client = DataClient::from("my-token")
+        .with_base_url(&base_url)
+        .unwrap();
+
+    let err = client.get_media("abc1234567").await.unwrap_err();
+
+    match err {
+        RustWistiaError::Request {
+            status_code, error, ..
+        } => {
+            assert_eq!(status_code, 404);
+            assert_eq!(error.message, "Media not found");
+        }
+        e => panic!("unexpected error: {e:?}"),
+    }
+
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_error_response_context() {
+    let body = r#"{"errors": {"name": ["can't be blank"]}}"#;
+    let (base_url, server) = serve(vec![
+        Reply::new("422 Unprocessable Entity", body).header("X-Request-Id", "req-123")
+    ])
+    .await;
+
+    let client = DataClient::from("my-token")
+        .with_base_url(&format!("{base_url}/v1"))
+        .unwrap();
+
+    let err = client
+        .update_media(UpdateMediaRequest::new("abc1234567").name(""))
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.request_id(), Some("req-123"));
+
+    let context = err.context().unwrap();
+    assert_eq!(context.method.as_ref().unwrap(), "PUT");
+    assert!(context
+        .url
+        .starts_with(&format!("{base_url}/v1/medias/abc1234567.json")));
+    assert_eq!(context.headers["x-request-id"], "req-123");
+    assert_eq!(context.body, body.as_bytes());
+
+    match err {
+        RustWistiaError::Request { error, .. } => {
+            assert_eq!(error.message, "name: can't be blank");
+            assert_eq!(error.field_errors["name"], ["can't be blank"]);
+        }
+        e => panic!("unexpected error: {e:?}"),
+    }
+
+    server.await.unwrap();
+}
+
+#[test]
+fn test_plain_http_base_url_requires_localhost() {
+    let err = DataClient::from("my-token")
+        .with_base_url("http://api.example.com/v1")
+        .err()
+        .unwrap();
+
+    assert!(matches!(err, RustWistiaError::InvalidBaseUrl { .. }));
+}
+
+#[tokio::test]
+async fn test_plain_http_request_requires_localhost() {
+    let client = DataClient::from("my-token");
+
+    let err = client
+        .get_raw("http://api.example.com/v1/medias.json?access_token=abc123")
+        .await
+        .unwrap_err();
+
+    match err {
+        RustWistiaError::InsecureUrl { url } => {
+            assert_eq!(
+                url,
+                "http://api.example.com/v1/medias.json?access_token=REDACTED"
+            );
+        }
+        e => panic!("unexpected error: {e:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_share_client_across_tasks() {
+    let (base_url, server) = serve(vec![("200 OK", MEDIA_JSON), ("200 OK", MEDIA_JSON)]).await;
+
+    let client = Arc::new(
+        DataClient::from("my-token")
+            .with_base_url(&base_url)
+            .unwrap(),
+    );
+
+    let tasks: Vec<_> = (0..2)
+        .map(|_| {
+            let client = Arc::clone(&client);
+            tokio::spawn(async move { client.get_media("abc1234567").await })
+        })
+        .collect();
+
+    for task in tasks {
+        assert_eq!(task.await.unwrap().unwrap().hashed_id, "abc1234567");
+    }
+
+    server.await.unwrap();
+}
@@ -0,0 +1,228 @@
+mod common;
+
+use common::MEDIA_JSON;
+#[cfg(feature = "upload-url")]
+use common::UPLOAD_JSON;
+use rust_wistia::mock::{Mock, MockTransport};
+use rust_wistia::models::{DownloadAssetRequest, Media};
+use rust_wistia::{DataClient, ErrorKind, RustWistiaError};
+#[cfg(feature = "upload-url")]
+use rust_wistia::{UploadAuth, UploadClient, UrlUploader, Wistia};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_data_client_with_mock() {
+    let transport = MockTransport::new();
+    transport
+        .mock(Mock::get("/v1/medias/abc1234567.json").body(MEDIA_JSON))
+        .mock(
+            Mock::get("/v1/medias/missing1234.json")
+                .status(404)
+                .json(&serde_json::json!({"error": "Media not found"})),
+        );
+
+    let client = DataClient::with_transport("my-token", transport.clone());
+
+    let media = client.get_media("abc1234567").await.unwrap();
+    assert_eq!(media.hashed_id, "abc1234567");
+
+    let err = client.get_media("missing1234").await.unwrap_err();
+    assert!(matches!(
+        err,
+        RustWistiaError::Request {
+            status_code: 404,
+            ..
+        }
+    ));
+
+    transport.assert_called_times("GET", "/v1/medias/abc1234567.json", 1);
+    transport.assert_called("GET", "/v1/medias/missing1234.json");
+
+    let call = &transport.calls()[0];
+    assert_eq!(call.header("Authorization").unwrap(), "Bearer my-token");
+}
+
+#[tokio::test]
+async fn test_media_id_is_encoded_in_path() {
+    let transport = MockTransport::new();
+    transport.mock(Mock::get("/v1/medias/%2E%2E%2Faccount%3Fx%3D1.json").body(MEDIA_JSON));
+
+    let client = DataClient::with_transport("my-token", transport.clone());
+    client.get_media("../account?x=1").await.unwrap();
+
+    transport.assert_called_times("GET", "/v1/medias/%2E%2E%2Faccount%3Fx%3D1.json", 1);
+}
+
+#[tokio::test]
+async fn test_get_media_from_embed_url() {
+    let transport = MockTransport::new();
+    transport.mock(Mock::get("/v1/medias/abc1234567.json").body(MEDIA_JSON));
+
+    let client = DataClient::with_transport("my-token", transport.clone());
+    client
+        .get_media("https://fast.wistia.net/embed/iframe/abc1234567?videoFoam=true")
+        .await
+        .unwrap();
+
+    transport.assert_called_times("GET", "/v1/medias/abc1234567.json", 1);
+}
+
+#[tokio::test]
+async fn test_raw_response() {
+    let transport = MockTransport::new();
+    transport
+        .mock(Mock::get("/v1/account.json").json(&serde_json::json!({"id": 1})))
+        .mock(Mock::delete("/v1/medias/abc1234567.json").status(204));
+
+    let client = DataClient::with_transport("my-token", transport.clone());
+
+    let resp = client
+        .get_raw(&client.api_url("account.json"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status, 200);
+    assert_eq!(resp.body.unwrap()["id"], 1);
+
+    let resp = client
+        .delete_raw(&client.api_url("medias/abc1234567.json"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status, 204);
+    assert_eq!(resp.body, None);
+}
+
+#[tokio::test]
+async fn test_unmatched_request_fails() {
+    let transport = MockTransport::new();
+    let client = DataClient::with_transport("my-token", transport.clone());
+
+    let err = client.get_media("abc1234567").await.unwrap_err();
+
+    assert!(matches!(err, RustWistiaError::Transport(_)));
+    assert_eq!(transport.calls().len(), 1);
+}
+
+#[cfg(feature = "upload-url")]
+#[tokio::test]
+async fn test_scripted_responses_in_order() {
+    let transport = MockTransport::new();
+    transport
+        .mock(Mock::post("/").status(503).times(1))
+        .mock(Mock::post("/").body(UPLOAD_JSON));
+
+    let wistia = Wistia::with_transport("my-token", transport.clone());
+    let uploader = wistia
+        .url_uploader("https://example.com/my/video.mp4")
+        .name("My Video");
+
+    // The transport is used as-is, so the first request is not retried
+    let err = uploader.send().await.unwrap_err();
+    assert!(matches!(
+        err,
+        RustWistiaError::Request {
+            status_code: 503,
+            ..
+        }
+    ));
+
+    let res = uploader.send().await.unwrap();
+    assert_eq!(res.hashed_id, "gn69c10tqw");
+
+    transport.assert_called_times("POST", "/", 2);
+    let call = &transport.calls()[1];
+    let body = String::from_utf8_lossy(&call.body);
+    assert_eq!(call.header("Authorization").unwrap(), "Bearer my-token");
+    assert!(!body.contains("access_token"));
+    assert!(body.contains("name=My+Video"));
+}
+
+#[cfg(feature = "upload-url")]
+#[tokio::test]
+async fn test_upload_auth() {
+    let transport = MockTransport::new();
+    transport.mock(Mock::post("/").body(UPLOAD_JSON));
+
+    for auth in [UploadAuth::Bearer, UploadAuth::Form, UploadAuth::Query] {
+        let client = UploadClient::with_transport("my-token", transport.clone()).with_auth(auth);
+        UrlUploader::with_client("https://example.com/my/video.mp4", client)
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let calls = transport.calls();
+    let bodies: Vec<_> = calls
+        .iter()
+        .map(|call| String::from_utf8_lossy(&call.body).into_owned())
+        .collect();
+
+    // Bearer
+    assert_eq!(calls[0].header("Authorization").unwrap(), "Bearer my-token");
+    assert!(calls[0].query.is_none());
+    assert!(!bodies[0].contains("access_token"));
+
+    // Form
+    assert!(calls[1].header("Authorization").is_none());
+    assert!(calls[1].query.is_none());
+    assert!(bodies[1].starts_with("access_token=my-token&"));
+
+    // Query
+    assert!(calls[2].header("Authorization").is_none());
+    assert_eq!(calls[2].query.as_deref(), Some("access_token=my-token"));
+    assert!(!bodies[2].contains("access_token"));
+}
+
+#[tokio::test]
+async fn test_error_kind() {
+    let transport = MockTransport::new();
+    transport
+        .mock(Mock::get("/v1/medias/missing1234.json").status(404))
+        .mock(Mock::get("/v1/medias/private123.json").status(403))
+        .mock(
+            Mock::get("/v1/medias/abc1234567.json")
+                .status(429)
+                .header("Retry-After", "30"),
+        );
+
+    let client = DataClient::with_transport("my-token", transport);
+
+    let err = client.get_media("missing1234").await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    assert_eq!(err.status(), Some(404));
+    assert!(!err.is_retryable());
+
+    let err = client.get_media("private123").await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Forbidden);
+
+    let err = client.get_media("abc1234567").await.unwrap_err();
+    assert_eq!(
+        err.kind(),
+        ErrorKind::RateLimited {
+            retry_after: Some(Duration::from_secs(30))
+        }
+    );
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn test_download_asset_to_writer() {
+    let transport = MockTransport::new();
+    transport.mock(Mock::get("/deliveries/abc123/file.mp4").body("video bytes"));
+
+    let mut media: Media = serde_json::from_str(MEDIA_JSON).unwrap();
+    media.assets = serde_json::from_str(
+        r#"[{"url": "http://embed.wistia.com/deliveries/abc123.bin", "width": 640, "height": 360, "fileSize": 11, "contentType": "video/mp4", "type": "OriginalFile"}]"#,
+    )
+    .unwrap();
+
+    let client = DataClient::with_transport("my-token", transport.clone());
+    let mut content = Vec::new();
+    let len = client
+        .download_asset_to(DownloadAssetRequest::from(media), &mut content)
+        .await
+        .unwrap();
+
+    assert_eq!(len, 11);
+    assert_eq!(content, b"video bytes");
+    assert_eq!(transport.calls().len(), 1);
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::{serve, serve_proxy, MEDIA_JSON};
+use rust_wistia::{ClientBuilder, Proxy, RetryPolicy, RustWistiaError};
+
+#[tokio::test]
+async fn test_connect_through_proxy() {
+    let (base_url, server) = serve([("200 OK", MEDIA_JSON)]).await;
+    let (proxy_url, proxy) = serve_proxy(Some("Basic dXNlcjpwYXNz")).await;
+
+    let client = ClientBuilder::new()
+        .access_token("my-token")
+        .base_url(&base_url)
+        .proxy(Proxy::all(&proxy_url).unwrap().basic_auth("user", "pass"))
+        .build_data()
+        .unwrap();
+
+    let media = client.get_media("abc1234567").await.unwrap();
+    assert_eq!(media.hashed_id, "abc1234567");
+
+    let target = base_url.trim_start_matches("http://");
+    let connect = proxy.await.unwrap();
+    assert!(connect.starts_with(&format!("CONNECT {target} HTTP/1.1\r\n")));
+
+    // The request itself is sent to the server through the tunnel
+    let requests = server.await.unwrap();
+    assert!(requests[0].starts_with("GET /medias/abc1234567.json"));
+}
+
+#[tokio::test]
+async fn test_proxy_credentials_in_url() {
+    let (base_url, server) = serve([("200 OK", MEDIA_JSON)]).await;
+    let (proxy_url, proxy) = serve_proxy(Some("Basic dXNlcjpwYXNz")).await;
+    let proxy_url = proxy_url.replace("http://", "http://user:pass@");
+
+    let client = ClientBuilder::new()
+        .access_token("my-token")
+        .base_url(&base_url)
+        .proxy(Proxy::all(&proxy_url).unwrap())
+        .build_data()
+        .unwrap();
+
+    client.get_media("abc1234567").await.unwrap();
+
+    proxy.await.unwrap();
+    assert_eq!(server.await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_proxy_authentication_required() {
+    let (proxy_url, proxy) = serve_proxy(Some("Basic dXNlcjpwYXNz")).await;
+
+    let client = ClientBuilder::new()
+        .access_token("my-token")
+        .base_url("http://localhost:9")
+        .proxy(Proxy::all(&proxy_url).unwrap())
+        .retry_policy(RetryPolicy::none())
+        .build_data()
+        .unwrap();
+
+    let err = client.get_media("abc1234567").await.unwrap_err();
+    assert!(matches!(err, RustWistiaError::Hyper(_)));
+    assert!(err.to_string().contains("proxy authentication required"));
+
+    let connect = proxy.await.unwrap();
+    assert!(connect.starts_with("CONNECT localhost:9 HTTP/1.1\r\n"));
+    assert!(!connect.contains("Proxy-Authorization"));
+}
+
+#[tokio::test]
+async fn test_no_proxy_connects_directly() {
+    let (base_url, server) = serve([("200 OK", MEDIA_JSON)]).await;
+
+    // Nothing listens on port 9, so the request fails if it uses the proxy
+    let client = ClientBuilder::new()
+        .access_token("my-token")
+        .base_url(&base_url)
+        .proxy(
+            Proxy::all("http://localhost:9")
+                .unwrap()
+                .no_proxy("example.com, 127.0.0.1"),
+        )
+        .build_data()
+        .unwrap();
+
+    client.get_media("abc1234567").await.unwrap();
+    assert_eq!(server.await.unwrap().len(), 1);
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use common::{serve, MEDIA_JSON};
+use rust_wistia::{ClientBuilder, RateLimit};
+
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn test_rate_limit_queues_requests() {
+    let (base_url, server) = serve([("200 OK", MEDIA_JSON); 4]).await;
+
+    // One request per 100 milliseconds, shared by all clients with the token
+    let builder = ClientBuilder::new()
+        .access_token("rate-limited-token")
+        .base_url(&base_url)
+        .rate_limit(RateLimit::per_minute(600).burst(1));
+
+    let client = builder.clone().build_data().unwrap();
+    let other = builder.build_data().unwrap();
+
+    let start = Instant::now();
+
+    let tasks: Vec<_> = [client.clone(), client, other.clone(), other]
+        .into_iter()
+        .map(|client| tokio::spawn(async move { client.get_media("abc1234567").await }))
+        .collect();
+
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+
+    assert!(start.elapsed() >= Duration::from_millis(300));
+    assert_eq!(server.await.unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn test_rate_limit_is_per_token() {
+    let (base_url, server) = serve([("200 OK", MEDIA_JSON); 2]).await;
+
+    // One request per 10 seconds, for each token
+    let client = ClientBuilder::new()
+        .access_token("rate-limited-tenant-a")
+        .base_url(&base_url)
+        .rate_limit(RateLimit::per_minute(6).burst(1))
+        .build_data()
+        .unwrap();
+
+    let start = Instant::now();
+
+    client.get_media("abc1234567").await.unwrap();
+    client
+        .for_token("rate-limited-tenant-b")
+        .get_media("abc1234567")
+        .await
+        .unwrap();
+
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(server.await.unwrap().len(), 2);
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use common::serve;
+#[cfg(all(feature = "tracing", feature = "upload-url"))]
+use common::UPLOAD_JSON;
+#[cfg(feature = "upload-url")]
+use rust_wistia::UrlUploader;
+use rust_wistia::{ClientBuilder, DataClient, RustWistiaError, UploadClient};
+
+const TOKEN: &str = "secret-token-123";
+
+#[tokio::test]
+async fn test_token_redacted_from_request_error() {
+    let (base_url, server) = serve(vec![(
+        "401 Unauthorized",
+        r#"{"error": "Invalid token: secret-token-123"}"#,
+    )])
+    .await;
+
+    let client = DataClient::from(TOKEN).with_base_url(&base_url).unwrap();
+    let err = client.get_media("abc1234567").await.unwrap_err();
+
+    assert!(!err.to_string().contains(TOKEN));
+    assert!(!format!("{err:?}").contains(TOKEN));
+
+    match err {
+        RustWistiaError::Request { reason, error, .. } => {
+            assert!(reason.starts_with("401 Client Error: Unauthorized for url:"));
+            assert_eq!(error.message, "Invalid token: REDACTED");
+        }
+        e => panic!("unexpected error: {e:?}"),
+    }
+
+    server.await.unwrap();
+}
+
+#[cfg(feature = "upload-url")]
+#[tokio::test]
+async fn test_token_redacted_from_upload_error() {
+    let (base_url, server) = serve(vec![(
+        "400 Bad Request",
+        "bad access_token=secret-token-123",
+    )])
+    .await;
+
+    let client = UploadClient::from(TOKEN)
+        .with_upload_url(&base_url)
+        .unwrap();
+    let err = UrlUploader::with_client("https://example.com/my/video.mp4", client)
+        .send()
+        .await
+        .unwrap_err();
+
+    assert!(!format!("{err} {err:?}").contains(TOKEN));
+
+    // The token is still sent to the server, of course
+    let requests = server.await.unwrap();
+    assert!(requests[0].contains(TOKEN));
+}
+
+#[test]
+fn test_token_redacted_from_debug() {
+    let builder = ClientBuilder::new().access_token(TOKEN);
+    let data = DataClient::from(TOKEN);
+    let upload = UploadClient::from(TOKEN);
+
+    for output in [
+        format!("{builder:?}"),
+        format!("{data:?}"),
+        format!("{upload:?}"),
+    ] {
+        assert!(!output.contains(TOKEN), "{output}");
+        assert!(output.contains("REDACTED"), "{output}");
+    }
+
+    let err = DataClient::from(TOKEN)
+        .with_base_url(&format!("http://localhost/v1?access_token={TOKEN}"))
+        .unwrap_err();
+    assert!(!format!("{err} {err:?}").contains(TOKEN));
+}
+
+#[cfg(all(feature = "tracing", feature = "upload-url"))]
+#[tokio::test]
+async fn test_tracing_span() {
+    use rust_wistia::RetryPolicy;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tracing_subscriber::fmt::format::FmtSpan;
+
+    /// Captures the formatted output of the subscriber.
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let output = Output::default();
+    let writer = output.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_span_events(FmtSpan::CLOSE)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let (base_url, server) = serve(vec![
+        ("503 Service Unavailable", ""),
+        ("200 OK", UPLOAD_JSON),
+    ])
+    .await;
+
+    let client = ClientBuilder::new()
+        .access_token(TOKEN)
+        .upload_url(&base_url)
+        .no_proxy()
+        .retry_policy(RetryPolicy::default().initial_backoff(Duration::from_millis(10)))
+        .build_upload()
+        .unwrap();
+
+    UrlUploader::with_client("https://example.com/my/video.mp4", client)
+        .retry_upload(true)
+        .send()
+        .await
+        .unwrap();
+    server.await.unwrap();
+
+    let output = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+
+    assert!(output.contains("wistia_api{"), "{output}");
+    assert!(output.contains(r#"api="upload""#), "{output}");
+    assert!(output.contains("method=POST"), "{output}");
+    assert!(
+        output.contains(&format!("endpoint={base_url}/")),
+        "{output}"
+    );
+    assert!(output.contains("status=200"), "{output}");
+    assert!(output.contains("retries=1"), "{output}");
+    assert!(output.contains("latency_ms="), "{output}");
+    assert!(output.contains("media_id="), "{output}");
+    assert!(!output.contains(TOKEN), "{output}");
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use common::{serve, serve_keep_alive, MEDIA_JSON};
+use rust_wistia::{AccessToken, ClientRegistry, RateLimit, RetryPolicy, RustWistiaError};
+use std::time::{Duration, Instant};
+
+/// Resolve the access token of a known tenant.
+fn resolve(tenant: &str) -> rust_wistia::Result<AccessToken> {
+    match tenant {
+        "tenant-1" => Ok(AccessToken::from("token-1")),
+        "tenant-2" => Ok(AccessToken::from("token-2")),
+        _ => Err(RustWistiaError::UnknownTenant {
+            tenant: tenant.to_owned(),
+        }),
+    }
+}
+
+#[tokio::test]
+async fn test_registry_shares_connection_pool() {
+    // The server only accepts a single connection, so each tenant must
+    // share the same connection pool.
+    let (base_url, server) = serve_keep_alive(vec![
+        ("200 OK", MEDIA_JSON),
+        ("200 OK", MEDIA_JSON),
+        ("200 OK", MEDIA_JSON),
+    ])
+    .await;
+
+    let registry = ClientRegistry::builder()
+        .base_url(&format!("{base_url}/v1"))
+        .no_proxy()
+        .build_registry(resolve)
+        .unwrap();
+
+    let media = registry
+        .get("tenant-1")
+        .unwrap()
+        .get_media("abc1234567")
+        .await
+        .unwrap();
+    assert_eq!(media.hashed_id, "abc1234567");
+
+    registry
+        .get("tenant-2")
+        .unwrap()
+        .get_media("abc1234567")
+        .await
+        .unwrap();
+    registry
+        .get("tenant-1")
+        .unwrap()
+        .get_media("abc1234567")
+        .await
+        .unwrap();
+
+    let requests = server.await.unwrap();
+    assert!(requests[0].contains("authorization: Bearer token-1\r\n"));
+    assert!(requests[1].contains("authorization: Bearer token-2\r\n"));
+    assert!(requests[2].contains("authorization: Bearer token-1\r\n"));
+
+    let mut tenants = registry.tenants();
+    tenants.sort();
+    assert_eq!(tenants, ["tenant-1", "tenant-2"]);
+
+    assert_eq!(registry.metrics("tenant-1").unwrap().requests, 2);
+    assert_eq!(registry.metrics("tenant-2").unwrap().requests, 1);
+}
+
+#[tokio::test]
+async fn test_registry_metrics() {
+    let (base_url, server) = serve(vec![
+        ("429 Too Many Requests", r#"{"error": "Slow down"}"#),
+        ("404 Not Found", r#"{"error": "Media not found"}"#),
+    ])
+    .await;
+
+    let registry = ClientRegistry::builder()
+        .base_url(&format!("{base_url}/v1"))
+        .no_proxy()
+        .retry_policy(RetryPolicy::none())
+        .build_registry(resolve)
+        .unwrap();
+
+    assert_eq!(registry.metrics("tenant-1"), None);
+
+    let client = registry.get("tenant-1").unwrap();
+    client.get_media("abc1234567").await.unwrap_err();
+    client.get_media("abc1234567").await.unwrap_err();
+    server.await.unwrap();
+
+    let metrics = registry.metrics("tenant-1").unwrap();
+    assert_eq!(metrics.requests, 2);
+    assert_eq!(metrics.errors, 2);
+    assert_eq!(metrics.rate_limited, 1);
+    assert!(metrics.average_latency().is_some());
+}
+
+#[test]
+fn test_registry_unknown_tenant() {
+    let registry = ClientRegistry::builder()
+        .no_proxy()
+        .build_registry(resolve)
+        .unwrap();
+
+    let err = registry.get("tenant-3").unwrap_err();
+    assert!(matches!(
+        err,
+        RustWistiaError::UnknownTenant { tenant } if tenant == "tenant-3"
+    ));
+    assert!(registry.tenants().is_empty());
+
+    registry.get("tenant-1").unwrap();
+    assert!(registry.remove("tenant-1"));
+    assert!(!registry.remove("tenant-1"));
+}
+
+#[tokio::test]
+async fn test_registry_tenant_rate_limit() {
+    let (base_url, server) = serve([("200 OK", MEDIA_JSON); 3]).await;
+
+    // One request per 100 milliseconds for each tenant
+    let registry = ClientRegistry::builder()
+        .base_url(&format!("{base_url}/v1"))
+        .no_proxy()
+        .tenant_rate_limit(RateLimit::per_minute(600).burst(1))
+        .build_registry(resolve)
+        .unwrap();
+
+    let tenant_1 = registry.get("tenant-1").unwrap();
+    let tenant_2 = registry.get("tenant-2").unwrap();
+    tenant_1.get_media("abc1234567").await.unwrap();
+
+    // Another tenant is not held up by the limit of `tenant-1`
+    let start = Instant::now();
+    tenant_2.get_media("abc1234567").await.unwrap();
+    assert!(start.elapsed() < Duration::from_millis(100));
+
+    tenant_1.get_media("abc1234567").await.unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(90));
+
+    server.await.unwrap();
+}
@@ -0,0 +1,127 @@
+mod common;
+
+#[cfg(feature = "upload-url")]
+use common::UPLOAD_JSON;
+use common::{serve, Reply, MEDIA_JSON};
+use rust_wistia::{ClientBuilder, RetryPolicy, RustWistiaError};
+
+use std::time::Duration;
+
+fn builder(base_url: &str) -> ClientBuilder {
+    ClientBuilder::new()
+        .access_token("my-token")
+        .base_url(base_url)
+        .upload_url(base_url)
+        .retry_policy(RetryPolicy::default().initial_backoff(Duration::from_millis(10)))
+}
+
+#[tokio::test]
+async fn test_retry_idempotent_request() {
+    let (base_url, server) = serve([
+        Reply::new("503 Service Unavailable", "").header("Retry-After", "0"),
+        Reply::new("200 OK", MEDIA_JSON),
+    ])
+    .await;
+
+    let client = builder(&base_url).build_data().unwrap();
+    let media = client.get_media("abc1234567").await.unwrap();
+
+    assert_eq!(media.hashed_id, "abc1234567");
+    assert_eq!(server.await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_retry_gives_up_after_max_attempts() {
+    let (base_url, server) = serve([
+        ("500 Internal Server Error", r#"{"error": "Oops"}"#),
+        ("502 Bad Gateway", r#"{"error": "Oops"}"#),
+    ])
+    .await;
+
+    let client = builder(&base_url)
+        .retry_policy(
+            RetryPolicy::default()
+                .max_attempts(2)
+                .initial_backoff(Duration::from_millis(10)),
+        )
+        .build_data()
+        .unwrap();
+
+    let err = client.get_media("abc1234567").await.unwrap_err();
+
+    assert!(matches!(
+        err,
+        RustWistiaError::Request {
+            status_code: 502,
+            ..
+        }
+    ));
+    assert_eq!(server.await.unwrap().len(), 2);
+}
+
+#[cfg(feature = "upload-url")]
+#[tokio::test]
+async fn test_upload_not_retried_by_default() {
+    let (base_url, server) = serve([("429 Too Many Requests", r#"{"error": "Slow down"}"#)]).await;
+
+    let client = builder(&base_url).build().unwrap();
+    let err = client
+        .url_uploader("https://example.com/my/video.mp4")
+        .send()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        RustWistiaError::Request {
+            status_code: 429,
+            ..
+        }
+    ));
+    assert_eq!(server.await.unwrap().len(), 1);
+}
+
+#[cfg(feature = "upload-url")]
+#[tokio::test]
+async fn test_upload_retried_when_opted_in() {
+    let (base_url, server) = serve([
+        Reply::new("429 Too Many Requests", "").header("Retry-After", "0"),
+        Reply::new("200 OK", UPLOAD_JSON),
+    ])
+    .await;
+
+    let client = builder(&base_url).build().unwrap();
+    let res = client
+        .url_uploader("https://example.com/my/video.mp4")
+        .retry_upload(true)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.hashed_id, "gn69c10tqw");
+
+    let requests = server.await.unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[0].split("\r\n\r\n").nth(1),
+        requests[1].split("\r\n\r\n").nth(1)
+    );
+}
+
+#[tokio::test]
+async fn test_retry_after_too_long() {
+    let (base_url, server) =
+        serve([Reply::new("503 Service Unavailable", "").header("Retry-After", "3600")]).await;
+
+    let client = builder(&base_url).build_data().unwrap();
+    let err = client.get_media("abc1234567").await.unwrap_err();
+
+    assert!(matches!(
+        err,
+        RustWistiaError::Request {
+            status_code: 503,
+            ..
+        }
+    ));
+    assert_eq!(server.await.unwrap().len(), 1);
+}
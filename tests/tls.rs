@@ -0,0 +1,234 @@
+mod common;
+
+use common::{read_request, Reply, MEDIA_JSON};
+use rust_wistia::{Certificate, ClientBuilder, Identity, RetryPolicy};
+
+use std::sync::Arc;
+
+use rcgen::{BasicConstraints, CertificateParams, DistinguishedName, DnType, IsCa};
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio_rustls::rustls::server::AllowAnyAuthenticatedClient;
+use tokio_rustls::rustls::{self, RootCertStore, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+
+/// A self-signed CA, which signs the certificates for the server and client.
+struct Ca(rcgen::Certificate);
+
+/// A certificate signed by the CA, along with its private key.
+struct Leaf {
+    cert_pem: String,
+    cert_der: Vec<u8>,
+    key_pem: String,
+    key_der: Vec<u8>,
+}
+
+impl Ca {
+    fn new() -> Self {
+        let mut params = CertificateParams::new(Vec::new());
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params.distinguished_name = common_name("rust-wistia test CA");
+
+        Self(rcgen::Certificate::from_params(params).unwrap())
+    }
+
+    fn pem(&self) -> String {
+        self.0.serialize_pem().unwrap()
+    }
+
+    fn der(&self) -> Vec<u8> {
+        self.0.serialize_der().unwrap()
+    }
+
+    fn sign(&self, name: &str) -> Leaf {
+        let mut params = CertificateParams::new(vec![name.to_owned()]);
+        params.distinguished_name = common_name(name);
+
+        let cert = rcgen::Certificate::from_params(params).unwrap();
+
+        Leaf {
+            cert_pem: cert.serialize_pem_with_signer(&self.0).unwrap(),
+            cert_der: cert.serialize_der_with_signer(&self.0).unwrap(),
+            key_pem: cert.serialize_private_key_pem(),
+            key_der: cert.serialize_private_key_der(),
+        }
+    }
+}
+
+/// A distinguished name, which must differ between the CA and the
+/// certificates it signs, as OpenSSL would otherwise consider them to be
+/// self-signed.
+fn common_name(name: &str) -> DistinguishedName {
+    let mut dn = DistinguishedName::new();
+    dn.push(DnType::CommonName, name);
+    dn
+}
+
+/// Start a local HTTPS server for `localhost`, which accepts a single
+/// connection, and requires a client certificate signed by `client_ca` (if
+/// any).
+///
+/// Returns the base URL of the server, and a handle which resolves to the
+/// raw request, or `None` if the TLS handshake failed.
+async fn serve_tls(server: &Leaf, client_ca: Option<&Ca>) -> (String, JoinHandle<Option<String>>) {
+    let builder = ServerConfig::builder().with_safe_defaults();
+
+    let builder = match client_ca {
+        Some(ca) => {
+            let mut roots = RootCertStore::empty();
+            roots.add(&rustls::Certificate(ca.der())).unwrap();
+            builder.with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots).boxed())
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let config = builder
+        .with_single_cert(
+            vec![rustls::Certificate(server.cert_der.clone())],
+            rustls::PrivateKey(server.key_der.clone()),
+        )
+        .unwrap();
+
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!(
+        "https://localhost:{}",
+        listener.local_addr().unwrap().port()
+    );
+
+    let handle = tokio::spawn(async move {
+        let (socket, _) = listener.accept().await.unwrap();
+        let mut stream = acceptor.accept(socket).await.ok()?;
+
+        let req = read_request(&mut stream).await;
+        let resp = Reply::new("200 OK", MEDIA_JSON).to_http(true);
+        stream.write_all(resp.as_bytes()).await.unwrap();
+        stream.shutdown().await.ok();
+
+        Some(req)
+    });
+
+    (base_url, handle)
+}
+
+fn builder(base_url: &str) -> ClientBuilder {
+    ClientBuilder::new()
+        .access_token("my-token")
+        .base_url(base_url)
+        .no_proxy()
+        .retry_policy(RetryPolicy::none())
+}
+
+#[tokio::test]
+async fn test_untrusted_certificate() {
+    let ca = Ca::new();
+    let (base_url, server) = serve_tls(&ca.sign("localhost"), None).await;
+
+    let client = builder(&base_url).build_data().unwrap();
+
+    assert!(client.get_media("abc1234567").await.is_err());
+    assert!(server.await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_add_root_certificate() {
+    let ca = Ca::new();
+    let (base_url, server) = serve_tls(&ca.sign("localhost"), None).await;
+
+    let client = builder(&base_url)
+        .add_root_certificate(Certificate::from_pem(ca.pem().as_bytes()).unwrap())
+        .build_data()
+        .unwrap();
+
+    let media = client.get_media("abc1234567").await.unwrap();
+    assert_eq!(media.hashed_id, "abc1234567");
+
+    let req = server.await.unwrap().unwrap();
+    assert!(req.starts_with("GET /medias/abc1234567.json"));
+}
+
+#[tokio::test]
+async fn test_client_certificate() {
+    let ca = Ca::new();
+    let client_ca = Ca::new();
+    let server_cert = ca.sign("localhost");
+    let client_cert = client_ca.sign("my-client");
+    let root = Certificate::from_der(&ca.der());
+
+    // The server requires a client certificate
+    let (base_url, server) = serve_tls(&server_cert, Some(&client_ca)).await;
+
+    let client = builder(&base_url)
+        .add_root_certificate(root.clone())
+        .build_data()
+        .unwrap();
+
+    assert!(client.get_media("abc1234567").await.is_err());
+    assert!(server.await.unwrap().is_none());
+
+    // ... which is presented with an identity
+    let (base_url, server) = serve_tls(&server_cert, Some(&client_ca)).await;
+
+    let identity = Identity::from_pem(
+        client_cert.cert_pem.as_bytes(),
+        client_cert.key_pem.as_bytes(),
+    )
+    .unwrap();
+
+    let client = builder(&base_url)
+        .add_root_certificate(root)
+        .identity(identity)
+        .build_data()
+        .unwrap();
+
+    client.get_media("abc1234567").await.unwrap();
+    assert!(server.await.unwrap().is_some());
+}
+
+#[cfg(feature = "rust-tls")]
+#[tokio::test]
+async fn test_rustls_config() {
+    use rust_wistia::https::rustls as client_rustls;
+
+    let ca = Ca::new();
+    let (base_url, server) = serve_tls(&ca.sign("localhost"), None).await;
+
+    let mut roots = client_rustls::RootCertStore::empty();
+    roots.add(&client_rustls::Certificate(ca.der())).unwrap();
+
+    let config = client_rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    let client = builder(&base_url)
+        .rustls_config(config)
+        .build_data()
+        .unwrap();
+
+    client.get_media("abc1234567").await.unwrap();
+    assert!(server.await.unwrap().is_some());
+}
+
+#[cfg(not(feature = "rust-tls"))]
+#[tokio::test]
+async fn test_native_tls_connector() {
+    use rust_wistia::https::native_tls;
+
+    let ca = Ca::new();
+    let (base_url, server) = serve_tls(&ca.sign("localhost"), None).await;
+
+    let connector = native_tls::TlsConnector::builder()
+        .add_root_certificate(native_tls::Certificate::from_der(&ca.der()).unwrap())
+        .build()
+        .unwrap();
+
+    let client = builder(&base_url)
+        .native_tls_connector(connector)
+        .build_data()
+        .unwrap();
+
+    client.get_media("abc1234567").await.unwrap();
+    assert!(server.await.unwrap().is_some());
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use common::MEDIA_JSON;
+#[cfg(feature = "upload-url")]
+use common::UPLOAD_JSON;
+use rust_wistia::mock::{Mock, MockTransport};
+use rust_wistia::{AccessToken, ClientBuilder, DataClient, FnToken, RustWistiaError};
+#[cfg(feature = "upload-url")]
+use rust_wistia::{FileToken, UploadClient, UrlUploader};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+#[cfg(feature = "upload-url")]
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_token_provider_consulted_per_request() {
+    let transport = MockTransport::new();
+    transport.mock(Mock::get("/v1/medias/abc1234567.json").body(MEDIA_JSON));
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let provider = {
+        let count = count.clone();
+        FnToken::new(move || {
+            let n = count.fetch_add(1, Ordering::SeqCst);
+            async move { Ok(AccessToken::from(format!("token-{n}"))) }
+        })
+    };
+
+    let client =
+        DataClient::with_transport("unused", transport.clone()).with_token_provider(provider);
+
+    client.get_media("abc1234567").await.unwrap();
+    client.get_media("abc1234567").await.unwrap();
+
+    let calls = transport.calls();
+    assert_eq!(calls[0].header("Authorization").unwrap(), "Bearer token-0");
+    assert_eq!(calls[1].header("Authorization").unwrap(), "Bearer token-1");
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_token_provider_error() {
+    let transport = MockTransport::new();
+
+    let client = DataClient::with_transport("unused", transport.clone()).with_token_provider(
+        FnToken::new(|| async {
+            Err(RustWistiaError::EnvVarNotFound {
+                name: "SECRET".to_owned(),
+            })
+        }),
+    );
+
+    let err = client.get_media("abc1234567").await.unwrap_err();
+    assert!(matches!(err, RustWistiaError::EnvVarNotFound { .. }));
+
+    transport.assert_no_calls();
+}
+
+#[cfg(feature = "upload-url")]
+#[tokio::test]
+async fn test_file_token_rotation() {
+    let path = std::env::temp_dir().join(format!("rust-wistia-rotate-{}", std::process::id()));
+    std::fs::write(&path, "first-token\n").unwrap();
+
+    let transport = MockTransport::new();
+    transport.mock(Mock::post("/").body(UPLOAD_JSON));
+
+    let client = UploadClient::with_transport("unused", transport.clone())
+        .with_token_provider(FileToken::new(&path).refresh_interval(Duration::ZERO));
+    let url = "https://example.com/my/video.mp4";
+
+    UrlUploader::with_client(url, client.clone())
+        .send()
+        .await
+        .unwrap();
+    std::fs::write(&path, "second-token-rotated\n").unwrap();
+    UrlUploader::with_client(url, client.clone())
+        .send()
+        .await
+        .unwrap();
+    // A rotation to a token of the same length is also picked up
+    std::fs::write(&path, "second-token-renewed\n").unwrap();
+    UrlUploader::with_client(url, client).send().await.unwrap();
+
+    std::fs::remove_file(&path).unwrap();
+
+    let calls = transport.calls();
+    assert_eq!(
+        calls[0].header("Authorization").unwrap(),
+        "Bearer first-token"
+    );
+    assert_eq!(
+        calls[1].header("Authorization").unwrap(),
+        "Bearer second-token-rotated"
+    );
+    assert_eq!(
+        calls[2].header("Authorization").unwrap(),
+        "Bearer second-token-renewed"
+    );
+}
+
+#[cfg(feature = "upload-url")]
+#[tokio::test]
+async fn test_for_token_override() {
+    let transport = MockTransport::new();
+    transport
+        .mock(Mock::get("/v1/medias/abc1234567.json").body(MEDIA_JSON))
+        .mock(Mock::post("/").body(UPLOAD_JSON));
+
+    let client = DataClient::with_transport("my-token", transport.clone());
+
+    client.get_media("abc1234567").await.unwrap();
+    client
+        .for_token("tenant-token")
+        .get_media("abc1234567")
+        .await
+        .unwrap();
+
+    let upload =
+        UploadClient::with_transport("my-token", transport.clone()).for_token("tenant-token");
+    UrlUploader::with_client("https://example.com/my/video.mp4", upload)
+        .send()
+        .await
+        .unwrap();
+
+    let calls = transport.calls();
+    assert_eq!(calls[0].header("Authorization").unwrap(), "Bearer my-token");
+    assert_eq!(
+        calls[1].header("Authorization").unwrap(),
+        "Bearer tenant-token"
+    );
+    assert_eq!(
+        calls[2].header("Authorization").unwrap(),
+        "Bearer tenant-token"
+    );
+}
+
+#[test]
+fn test_builder_token_provider() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let provider = {
+        let count = count.clone();
+        FnToken::new(move || {
+            count.fetch_add(1, Ordering::SeqCst);
+            async { Ok(AccessToken::from("provided-token")) }
+        })
+    };
+
+    // The provider takes precedence over the access token, and is only
+    // consulted when a request is sent.
+    let client = ClientBuilder::new()
+        .access_token("my-token")
+        .token_provider(provider)
+        .no_proxy()
+        .build_data()
+        .unwrap();
+
+    assert!(format!("{client:?}").contains("REDACTED"));
+    assert_eq!(count.load(Ordering::SeqCst), 0);
+
+    let token = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.access_token())
+        .unwrap();
+    assert_eq!(token.expose_secret(), "provided-token");
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+}
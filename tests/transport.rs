@@ -0,0 +1,141 @@
+mod common;
+
+use common::{serve, Reply, MEDIA_JSON};
+use rust_wistia::{
+    BoxTransport, ClientBuilder, HttpsTransport, RetryLayer, RetryPolicy, RustWistiaError,
+};
+
+use std::future::{poll_fn, Future};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use hyper::{Body, Request, Response};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// A layer which counts requests, and fails any request once `fail_after`
+/// requests have been sent.
+#[derive(Clone)]
+struct CountLayer {
+    count: Arc<AtomicUsize>,
+    fail_after: usize,
+}
+
+#[derive(Clone)]
+struct CountService<S> {
+    inner: S,
+    layer: CountLayer,
+}
+
+impl<S> Layer<S> for CountLayer {
+    type Service = CountService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CountService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+impl<S> Service<Request<Body>> for CountService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>, Error = RustWistiaError>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+    type Future = Pin<Box<dyn Future<Output = Result<Response<Body>, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        if self.layer.count.fetch_add(1, Ordering::SeqCst) >= self.layer.fail_after {
+            return Box::pin(async { Err("too many requests".into()) });
+        }
+
+        let fut = self.inner.call(req);
+        Box::pin(async move { Ok(fut.await?) })
+    }
+}
+
+#[tokio::test]
+async fn test_custom_layer() {
+    let (base_url, server) = serve([("200 OK", MEDIA_JSON)]).await;
+    let count = Arc::new(AtomicUsize::new(0));
+
+    let client = ClientBuilder::new()
+        .access_token("my-token")
+        .base_url(&base_url)
+        .layer(CountLayer {
+            count: count.clone(),
+            fail_after: 1,
+        })
+        .build_data()
+        .unwrap();
+
+    client.get_media("abc1234567").await.unwrap();
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+
+    // Errors raised by the layer are returned as-is
+    let err = client.get_media("abc1234567").await.unwrap_err();
+    assert!(matches!(err, RustWistiaError::Transport(_)));
+    assert_eq!(err.to_string(), "transport error: too many requests");
+
+    assert_eq!(server.await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_retry_layer_on_transport() {
+    let (base_url, server) = serve([
+        Reply::new("503 Service Unavailable", "").header("Retry-After", "0"),
+        Reply::new("200 OK", MEDIA_JSON),
+    ])
+    .await;
+
+    let policy = RetryPolicy::default().initial_backoff(Duration::from_millis(10));
+    let https = HttpsTransport::new(ClientBuilder::new().https_client().unwrap());
+    let mut transport = BoxTransport::new(RetryLayer::new(policy).layer(https));
+
+    let req = Request::get(format!("{base_url}/medias/abc1234567.json"))
+        .body(Body::empty())
+        .unwrap();
+
+    poll_fn(|cx| transport.poll_ready(cx)).await.unwrap();
+    let resp = transport.call(req).await.unwrap();
+
+    assert_eq!(resp.status(), 200);
+    assert_eq!(server.await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_read_timeout_allows_slow_upload() {
+    let (base_url, server) = serve([("200 OK", MEDIA_JSON)]).await;
+
+    let https = ClientBuilder::new().https_client().unwrap();
+    let mut transport = HttpsTransport::new(https).read_timeout(Some(Duration::from_millis(100)));
+
+    // The body takes longer than the read timeout to send, but a chunk is
+    // sent well within it each time
+    let (mut sender, body) = Body::channel();
+    tokio::spawn(async move {
+        for _ in 0..5 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            sender.send_data("0123456789".into()).await.unwrap();
+        }
+    });
+
+    let req = Request::post(format!("{base_url}/"))
+        .header("Content-Length", "50")
+        .body(body)
+        .unwrap();
+    let resp = transport.call(req).await.unwrap();
+
+    assert_eq!(resp.status(), 200);
+    assert!(server.await.unwrap()[0].ends_with(&"0123456789".repeat(5)));
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "upload-url")]
+
+mod common;
+
+use common::{serve_keep_alive, MEDIA_JSON, MEDIA_STATS_JSON, UPLOAD_JSON};
+use rust_wistia::Wistia;
+
+use std::time::Duration;
+
+use tokio::time::timeout;
+
+#[tokio::test]
+async fn test_clients_share_connection_pool() {
+    let (base_url, server) = serve_keep_alive(vec![
+        ("200 OK", MEDIA_JSON),
+        ("200 OK", MEDIA_STATS_JSON),
+        ("200 OK", UPLOAD_JSON),
+    ])
+    .await;
+
+    let wistia = Wistia::from("my-token")
+        .with_base_url(&base_url)
+        .unwrap()
+        .with_upload_url(&base_url)
+        .unwrap();
+
+    // The server only accepts a single connection, so each request would
+    // time out if it were sent on a new connection.
+    let wait = Duration::from_secs(5);
+
+    let media = timeout(wait, wistia.data().get_media("abc1234567"))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(media.hashed_id, "abc1234567");
+
+    let stats = timeout(wait, wistia.stats().media(&media.hashed_id))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stats.play_count, 2);
+
+    let uploader = wistia
+        .url_uploader("https://example.com/my/video.mp4")
+        .name("My Video");
+    let res = timeout(wait, uploader.send()).await.unwrap().unwrap();
+    assert_eq!(res.hashed_id, "gn69c10tqw");
+
+    let requests = server.await.unwrap();
+    assert!(requests[0].starts_with("GET /medias/abc1234567.json "));
+    assert!(requests[1].starts_with("GET /stats/medias/abc1234567.json "));
+    assert!(requests[2].starts_with("POST / "));
+    assert!(requests[2].ends_with("&name=My+Video"));
+}